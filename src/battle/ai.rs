@@ -0,0 +1,492 @@
+use super::damage::calculate_damage;
+use super::error::ActionError;
+use super::rng::{BattleRng, SplitMix64};
+use super::view::{BattleView, OpponentCreature};
+use super::{Battle, BattleAction, BattleState};
+use crate::creature::{Creature, CreatureId};
+use crate::creature_type::CreatureType;
+use crate::moves::{HitCount, Move, MoveCategory, MoveId, MoveRegistry};
+
+/// How much better a benched creature's matchup must be before
+/// [`SwitchingAi`] swaps it in
+const SWITCH_MARGIN: f32 = 2.0;
+
+/// Chooses actions for a side nobody is controlling, such as a trainer
+pub trait BattleAi<M: MoveRegistry> {
+    /// Picks one action for each of the side's active creatures still able to fight
+    fn choose_actions(&mut self, view: &BattleView<'_, M>) -> Vec<BattleAction>;
+
+    /// Picks the benched creature to send in for a fainted one
+    ///
+    /// Only asked while the side has a replacement pending, so at least one
+    /// benched creature can fight. Defaults to the first of them.
+    fn choose_replacement(&mut self, view: &BattleView<'_, M>) -> CreatureId {
+        view.bench()
+            .iter()
+            .find(|c| !c.is_fainted())
+            .expect("a pending replacement has a benched creature able to fight")
+            .id
+    }
+}
+
+/// Uses a random move it has PP for against a random opponent
+pub struct RandomAi {
+    rng: SplitMix64,
+}
+
+impl RandomAi {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            rng: SplitMix64::new(seed),
+        }
+    }
+}
+
+impl<M: MoveRegistry> BattleAi<M> for RandomAi {
+    fn choose_actions(&mut self, view: &BattleView<'_, M>) -> Vec<BattleAction> {
+        let foes = living_foes(view);
+        acting_slots(view)
+            .map(|(slot, creature)| {
+                if foes.is_empty() {
+                    return BattleAction::Pass;
+                }
+                let target = &foes[self.rng.below(foes.len() as u32) as usize];
+                let usable = view.usable_moves(slot);
+                if usable.is_empty() {
+                    return struggle(creature, target);
+                }
+                let pick = self.rng.below(usable.len() as u32) as usize;
+                BattleAction::Attack {
+                    attacker_id: creature.id,
+                    target_id: target.id,
+                    move_id: usable[pick].clone(),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Always uses the move and target with the highest expected damage
+#[derive(Debug, Default)]
+pub struct GreedyAi;
+
+impl GreedyAi {
+    /// The most damaging attack the creature in `slot` can make right now
+    fn best_attack<M: MoveRegistry>(
+        view: &BattleView<'_, M>,
+        slot: usize,
+        creature: &Creature,
+    ) -> BattleAction {
+        let foes = living_foes(view);
+        let mut best: Option<(f32, MoveId, &OpponentCreature)> = None;
+        for move_id in view.usable_moves(slot) {
+            let Some(mv) = view.move_data(&move_id) else {
+                continue;
+            };
+            for foe in &foes {
+                let score = expected_damage(creature, mv, &foe.types);
+                if best.as_ref().is_none_or(|(top, ..)| score > *top) {
+                    best = Some((score, move_id.clone(), foe));
+                }
+            }
+        }
+        match best {
+            Some((_, move_id, target)) => BattleAction::Attack {
+                attacker_id: creature.id,
+                target_id: target.id,
+                move_id,
+            },
+            None => match foes.first() {
+                Some(target) => struggle(creature, target),
+                None => BattleAction::Pass,
+            },
+        }
+    }
+}
+
+impl<M: MoveRegistry> BattleAi<M> for GreedyAi {
+    fn choose_actions(&mut self, view: &BattleView<'_, M>) -> Vec<BattleAction> {
+        acting_slots(view)
+            .map(|(slot, creature)| Self::best_attack(view, slot, creature))
+            .collect()
+    }
+}
+
+/// Attacks like [`GreedyAi`], but switches out of matchups that are going badly
+///
+/// A creature is swapped out when the opponents' types threaten it and a
+/// benched creature fares at least twice as well against them.
+#[derive(Debug, Default)]
+pub struct SwitchingAi;
+
+impl SwitchingAi {
+    /// The benched creature that fares best against the living opponents
+    fn best_on_bench<'v, M: MoveRegistry>(
+        view: &BattleView<'v, M>,
+        taken: &[CreatureId],
+    ) -> Option<(f32, &'v Creature)> {
+        let foes = living_foes(view);
+        view.bench()
+            .iter()
+            .filter(|c| !c.is_fainted() && !taken.contains(&c.id))
+            .map(|c| (matchup(view, c, &foes), c))
+            .max_by(|(a, _), (b, _)| a.total_cmp(b))
+    }
+}
+
+impl<M: MoveRegistry> BattleAi<M> for SwitchingAi {
+    fn choose_actions(&mut self, view: &BattleView<'_, M>) -> Vec<BattleAction> {
+        let foes = living_foes(view);
+        let mut taken = Vec::new();
+        acting_slots(view)
+            .map(|(slot, creature)| {
+                let threatened = foes.iter().any(|foe| threat(&foe.types, creature) > 1.0);
+                if threatened && view.can_switch(slot) {
+                    let current = matchup(view, creature, &foes);
+                    if let Some((score, bench)) = Self::best_on_bench(view, &taken)
+                        && score >= current * SWITCH_MARGIN
+                    {
+                        taken.push(bench.id);
+                        return BattleAction::Switch {
+                            out_id: creature.id,
+                            in_id: bench.id,
+                        };
+                    }
+                }
+                GreedyAi::best_attack(view, slot, creature)
+            })
+            .collect()
+    }
+
+    fn choose_replacement(&mut self, view: &BattleView<'_, M>) -> CreatureId {
+        Self::best_on_bench(view, &[])
+            .expect("a pending replacement has a benched creature able to fight")
+            .1
+            .id
+    }
+}
+
+impl<M: MoveRegistry> Battle<'_, M> {
+    /// Lets `ai` make the choices `side` currently owes the battle
+    ///
+    /// While actions are being selected it submits an action for every
+    /// active creature; while fainted creatures are being replaced it sends
+    /// in the AI's pick if `side` has one to replace.
+    pub fn submit_ai_choices(
+        &mut self,
+        side: usize,
+        ai: &mut dyn BattleAi<M>,
+    ) -> Result<(), ActionError> {
+        match self.state {
+            BattleState::SelectActions => {
+                let actions = ai.choose_actions(&self.view(side));
+                actions
+                    .into_iter()
+                    .try_for_each(|action| self.submit_action(action))
+            }
+            BattleState::ReplaceFainted => {
+                if !self.pending_replacements().contains(&side) {
+                    return Ok(());
+                }
+                let in_id = ai.choose_replacement(&self.view(side));
+                self.replace_fainted(side, in_id)
+            }
+            _ => Err(ActionError::WrongState),
+        }
+    }
+}
+
+/// Active slots of the view's side holding a creature that can act
+fn acting_slots<'v, M: MoveRegistry>(
+    view: &BattleView<'v, M>,
+) -> impl Iterator<Item = (usize, &'v Creature)> {
+    view.actives()
+        .iter()
+        .enumerate()
+        .filter(|(_, c)| !c.is_fainted())
+}
+
+/// Active opponents that have not fainted
+///
+/// Empty when there is nothing left to attack, in which case the AIs pass.
+fn living_foes<M: MoveRegistry>(view: &BattleView<'_, M>) -> Vec<OpponentCreature> {
+    view.opponent_actives()
+        .into_iter()
+        .filter(|c| !c.is_fainted())
+        .collect()
+}
+
+fn struggle(creature: &Creature, target: &OpponentCreature) -> BattleAction {
+    BattleAction::Struggle {
+        attacker_id: creature.id,
+        target_id: target.id,
+    }
+}
+
+/// Rough damage `mv` would deal from `user` to a foe of `foe_types`,
+/// ignoring stages, weighted by its accuracy and average number of hits
+///
+/// The foe's stats are hidden, so it is taken to be as sturdy as the user.
+pub fn expected_damage(user: &Creature, mv: &Move, foe_types: &[CreatureType]) -> f32 {
+    if mv.category == MoveCategory::Status || mv.power == 0 {
+        return 0.0;
+    }
+    let stats = &user.individual_stats;
+    let (attack, defense) = match mv.category {
+        MoveCategory::Special => (stats.special_attack, stats.special_defense),
+        _ => (stats.attack, stats.defense),
+    };
+    let power = mv.effective_power(&user.types, foe_types);
+    let damage = calculate_damage(user.level, attack.get(), defense.get(), power) as f32;
+    let hits = match mv.hits {
+        HitCount::Once => 1.0,
+        HitCount::Fixed(n) => n as f32,
+        HitCount::Between { min, max } => (min + max) as f32 / 2.0,
+    };
+    let accuracy = mv.accuracy.map_or(1.0, |a| a.min(100) as f32 / 100.0);
+    damage * hits * accuracy
+}
+
+/// Strongest type multiplier a foe of `foe_types` has against `defender`
+/// from moves of its own types
+fn threat(foe_types: &[CreatureType], defender: &Creature) -> f32 {
+    foe_types
+        .iter()
+        .map(|&t| CreatureType::combined_multiplier(t, &defender.types))
+        .fold(0.0, f32::max)
+}
+
+/// How well `creature` would fare against its worst-matched foe
+///
+/// Compares the best effective power among its damaging moves with how hard
+/// the foe's types threaten it; higher is better.
+fn matchup<M: MoveRegistry>(
+    view: &BattleView<'_, M>,
+    creature: &Creature,
+    foes: &[OpponentCreature],
+) -> f32 {
+    foes.iter()
+        .map(|foe| {
+            let offense = creature
+                .moves
+                .iter()
+                .flatten()
+                .filter_map(|known| view.move_data(&known.move_id))
+                .filter(|mv| mv.category != MoveCategory::Status)
+                .map(|mv| mv.effective_power(&creature.types, &foe.types))
+                .fold(0.0, f32::max);
+            // Keep an immunity from dividing by zero
+            offense / threat(&foe.types, creature).max(0.25)
+        })
+        .fold(f32::INFINITY, f32::min)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::battle::format::BattleFormat;
+    use crate::battle::volatile::Volatile;
+    use crate::creature_type::CreatureType::*;
+    use crate::encounter::Encounter;
+    use crate::tests::helpers::{MockMoveRegistry, battle, creature};
+
+    fn chosen_move(action: &BattleAction) -> MoveId {
+        match action {
+            BattleAction::Attack { move_id, .. } => move_id.clone(),
+            other => panic!("expected an attack, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn random_ai_only_uses_moves_with_pp() {
+        let moves = MockMoveRegistry::new();
+        let mut ours = creature(&[Grass], &[1, 2]);
+        ours.moves[0].as_mut().unwrap().pp.current = 0;
+        let battle = battle(
+            &moves,
+            BattleFormat::Single,
+            vec![ours],
+            vec![creature(&[Grass], &[])],
+        );
+
+        for seed in 0..20 {
+            let actions = RandomAi::new(seed).choose_actions(&battle.view(0));
+            assert_eq!(actions.len(), 1);
+            assert_eq!(chosen_move(&actions[0]), MoveId(2));
+        }
+    }
+
+    #[test]
+    fn random_ai_struggles_without_usable_moves() {
+        let moves = MockMoveRegistry::new();
+        let battle = battle(
+            &moves,
+            BattleFormat::Single,
+            vec![creature(&[Grass], &[])],
+            vec![creature(&[Grass], &[])],
+        );
+
+        let actions = RandomAi::new(0).choose_actions(&battle.view(0));
+        assert!(matches!(actions[..], [BattleAction::Struggle { .. }]));
+    }
+
+    #[test]
+    fn ais_pass_when_no_foe_is_left_standing() {
+        let moves = MockMoveRegistry::new();
+        let mut battle = battle(
+            &moves,
+            BattleFormat::Single,
+            vec![creature(&[Grass], &[1])],
+            vec![creature(&[Grass], &[1])],
+        );
+        battle.parties[1].creatures[0].current_hp = 0;
+        let view = battle.view(0);
+
+        let ais: [&mut dyn BattleAi<MockMoveRegistry>; 3] =
+            [&mut RandomAi::new(0), &mut GreedyAi, &mut SwitchingAi];
+        for ai in ais {
+            assert!(matches!(ai.choose_actions(&view)[..], [BattleAction::Pass]));
+        }
+    }
+
+    #[test]
+    fn greedy_ai_prefers_effective_moves_over_raw_power() {
+        let moves = MockMoveRegistry::new();
+        // Water Gun: 80 * 0.5 = 40, Fire Wheel: 20 * 2 * 1.5 STAB = 60
+        let battle = battle(
+            &moves,
+            BattleFormat::Single,
+            vec![creature(&[Fire], &[1, 2])],
+            vec![creature(&[Grass], &[])],
+        );
+
+        let actions = GreedyAi.choose_actions(&battle.view(0));
+        assert_eq!(chosen_move(&actions[0]), MoveId(2));
+    }
+
+    #[test]
+    fn greedy_ai_targets_the_foe_it_hurts_most() {
+        let moves = MockMoveRegistry::new();
+        let battle = battle(
+            &moves,
+            BattleFormat::Double,
+            vec![creature(&[Grass], &[3]), creature(&[Grass], &[3])],
+            vec![creature(&[Fire], &[]), creature(&[Water], &[])],
+        );
+        let weak_to_grass = battle.active(1, 1).id;
+
+        let actions = GreedyAi.choose_actions(&battle.view(0));
+        assert_eq!(actions.len(), 2);
+        for action in actions {
+            assert!(matches!(
+                action,
+                BattleAction::Attack { target_id, .. } if target_id == weak_to_grass
+            ));
+        }
+    }
+
+    #[test]
+    fn expected_damage_accounts_for_accuracy_and_hits() {
+        let mut moves = MockMoveRegistry::new();
+        let user = creature(&[Normal], &[]);
+        let sure = expected_damage(&user, moves.get(&MoveId(2)).unwrap(), &[Normal]);
+
+        let mv = moves.get_mut(&MoveId(2)).unwrap();
+        mv.accuracy = Some(50);
+        mv.hits = HitCount::Fixed(2);
+        let risky = expected_damage(&user, moves.get(&MoveId(2)).unwrap(), &[Normal]);
+        assert_eq!(risky, sure);
+    }
+
+    #[test]
+    fn switching_ai_leaves_a_bad_matchup() {
+        let moves = MockMoveRegistry::new();
+        let battle = battle(
+            &moves,
+            BattleFormat::Single,
+            vec![creature(&[Grass], &[3]), creature(&[Water], &[1])],
+            vec![creature(&[Fire], &[2])],
+        );
+        let bench = battle.parties[0].creatures[1].id;
+
+        let actions = SwitchingAi.choose_actions(&battle.view(0));
+        assert!(matches!(
+            actions[..],
+            [BattleAction::Switch { in_id, .. }] if in_id == bench
+        ));
+    }
+
+    #[test]
+    fn switching_ai_attacks_when_not_threatened() {
+        let moves = MockMoveRegistry::new();
+        let battle = battle(
+            &moves,
+            BattleFormat::Single,
+            vec![creature(&[Water], &[1]), creature(&[Grass], &[3])],
+            vec![creature(&[Fire], &[2])],
+        );
+
+        let actions = SwitchingAi.choose_actions(&battle.view(0));
+        assert_eq!(chosen_move(&actions[0]), MoveId(1));
+    }
+
+    #[test]
+    fn switching_ai_stays_in_while_trapped() {
+        let moves = MockMoveRegistry::new();
+        let mut battle = battle(
+            &moves,
+            BattleFormat::Single,
+            vec![creature(&[Grass], &[3]), creature(&[Water], &[1])],
+            vec![creature(&[Fire], &[2])],
+        );
+        battle.add_volatile((0, 0), Volatile::Trapped, (1, 0));
+
+        let actions = SwitchingAi.choose_actions(&battle.view(0));
+        assert_eq!(chosen_move(&actions[0]), MoveId(3));
+    }
+
+    #[test]
+    fn switching_ai_replaces_with_its_best_matchup() {
+        let moves = MockMoveRegistry::new();
+        let battle = battle(
+            &moves,
+            BattleFormat::Single,
+            vec![
+                creature(&[Grass], &[3]),
+                creature(&[Grass], &[3]),
+                creature(&[Water], &[1]),
+            ],
+            vec![creature(&[Fire], &[2])],
+        );
+
+        let pick = SwitchingAi.choose_replacement(&battle.view(0));
+        assert_eq!(pick, battle.parties[0].creatures[2].id);
+    }
+
+    #[test]
+    fn ais_can_play_a_battle_to_the_end() {
+        let moves = MockMoveRegistry::new();
+        let mut battle = battle(
+            &moves,
+            BattleFormat::Single,
+            vec![creature(&[Fire], &[2]), creature(&[Water], &[1])],
+            vec![creature(&[Grass], &[3]), creature(&[Water], &[1, 2])],
+        );
+        let mut ais: [Box<dyn BattleAi<MockMoveRegistry>>; 2] =
+            [Box::new(SwitchingAi), Box::new(RandomAi::new(9))];
+
+        while !battle.is_over() {
+            for (side, ai) in ais.iter_mut().enumerate() {
+                if matches!(
+                    battle.state,
+                    BattleState::SelectActions | BattleState::ReplaceFainted
+                ) {
+                    battle.submit_ai_choices(side, ai.as_mut()).unwrap();
+                }
+            }
+            battle.process_turn();
+        }
+
+        assert!(battle.outcome().is_some());
+    }
+}
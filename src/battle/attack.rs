@@ -0,0 +1,280 @@
+use super::stages::{BattleStat, StatStages};
+use super::{Battle, critical, damage};
+use crate::creature::CreatureId;
+use crate::event::BattleEvent;
+use crate::moves::{HitCount, Move, MoveCategory, MoveId, MoveRegistry, MoveTarget};
+
+/// Damage multiplier applied when a move hits more than one target
+const SPREAD_MODIFIER: f32 = 0.75;
+
+impl<M: MoveRegistry> Battle<'_, M> {
+    /// Uses `mv` from the attacker's slot against the targets it resolves to
+    ///
+    /// `chosen` is the slot the attacker aimed at when the turn began, if it
+    /// was an active creature.
+    pub(super) fn resolve_attack(
+        &mut self,
+        attacker_id: CreatureId,
+        chosen: Option<(usize, usize)>,
+        mv: &Move,
+    ) {
+        let Some(user) = self.locate_active(attacker_id) else {
+            return;
+        };
+        if self.active(user.0, user.1).is_fainted()
+            || !self.can_act(user)
+            || !self.volatiles_allow_move(user, mv)
+        {
+            return;
+        }
+        self.spend_pp(user, &mv.id);
+        self.reveal_move(user, &mv.id);
+
+        let targets = self.resolve_targets(user, chosen, mv.target);
+        let damaging = mv.category != MoveCategory::Status && mv.power > 0;
+        let spread = targets.len() > 1;
+        let hits = if damaging { self.roll_hits(mv.hits) } else { 0 };
+        let mut dealt = 0u16;
+        let mut landed = Vec::with_capacity(targets.len());
+        for target in targets {
+            if target == user {
+                landed.push(target);
+                continue;
+            }
+            if self.is_protected_from(user, target) {
+                self.events.push(BattleEvent::Protected {
+                    source: attacker_id,
+                    target: self.active(target.0, target.1).id,
+                });
+                continue;
+            }
+            if damaging && self.is_immune_to(user, target, mv) {
+                self.events.push(BattleEvent::NoEffect {
+                    source: attacker_id,
+                    target: self.active(target.0, target.1).id,
+                });
+                continue;
+            }
+            if !self.accuracy_check(user, target, mv) {
+                self.events.push(BattleEvent::Miss {
+                    source: attacker_id,
+                    target: self.active(target.0, target.1).id,
+                });
+                continue;
+            }
+            for _ in 0..hits {
+                if self.active(target.0, target.1).is_fainted()
+                    || self.active(user.0, user.1).is_fainted()
+                {
+                    break;
+                }
+                dealt = dealt.saturating_add(self.deal_damage(user, target, mv, spread));
+            }
+            landed.push(target);
+        }
+        if damaging {
+            self.apply_recoil_and_drain(user, mv, dealt);
+        }
+        if !landed.is_empty() || mv.target == MoveTarget::Field {
+            self.apply_move_effects(user, mv, &landed);
+        }
+    }
+
+    /// Takes one PP from the move if the creature in `user` knows it
+    fn spend_pp(&mut self, (side, slot): (usize, usize), move_id: &MoveId) {
+        let creature = &mut self.parties[side].all_mut()[slot];
+        if let Some(known) = creature
+            .moves
+            .iter_mut()
+            .flatten()
+            .find(|m| m.move_id == *move_id)
+        {
+            known.pp.current = known.pp.current.saturating_sub(1);
+        }
+    }
+
+    /// Rolls how many times a move strikes each target this use
+    fn roll_hits(&mut self, hits: HitCount) -> u8 {
+        match hits {
+            HitCount::Once => 1,
+            HitCount::Fixed(n) => n,
+            HitCount::Between { min, max } => self.rng.range(min as u32, max as u32) as u8,
+        }
+    }
+
+    /// Hurts or heals the user in `user` by its move's share of `dealt`
+    ///
+    /// Recoil is reported as damage the user deals to itself.
+    fn apply_recoil_and_drain(&mut self, user: (usize, usize), mv: &Move, dealt: u16) {
+        let attacker = self.active(user.0, user.1);
+        if dealt == 0 || attacker.is_fainted() {
+            return;
+        }
+        let id = attacker.id;
+        if let Some(recoil) = mv.recoil {
+            self.apply_damage(id, user, recoil.of(dealt).max(1));
+        }
+        if let Some(drain) = mv.drain
+            && !self.active(user.0, user.1).is_fainted()
+        {
+            self.heal(id, user, drain.of(dealt).max(1));
+        }
+    }
+
+    /// Works out which active slots a move used from `user` actually affects
+    ///
+    /// A single-target move whose chosen target has fainted is redirected to
+    /// the first opposing creature still standing.
+    pub(super) fn resolve_targets(
+        &self,
+        user: (usize, usize),
+        chosen: Option<(usize, usize)>,
+        target: MoveTarget,
+    ) -> Vec<(usize, usize)> {
+        let living = |side: usize| {
+            (0..self.active_count(side))
+                .filter(move |&slot| !self.active(side, slot).is_fainted())
+                .map(move |slot| (side, slot))
+        };
+        let (own_side, foe_side) = (user.0, 1 - user.0);
+
+        match target {
+            MoveTarget::Single => match chosen {
+                Some(pos) if pos == user => Vec::new(),
+                Some((side, slot)) if !self.active(side, slot).is_fainted() => vec![(side, slot)],
+                Some(_) => living(foe_side).take(1).collect(),
+                None => Vec::new(),
+            },
+            MoveTarget::AllOpponents => living(foe_side).collect(),
+            MoveTarget::User => vec![user],
+            MoveTarget::Ally => living(own_side).filter(|&pos| pos != user).collect(),
+            MoveTarget::AllOthers => living(foe_side)
+                .chain(living(own_side).filter(|&pos| pos != user))
+                .collect(),
+            MoveTarget::Field => Vec::new(),
+        }
+    }
+
+    /// Rolls whether a move used from `user` hits the creature in `target`
+    ///
+    /// The user's accuracy stage and the target's evasion stage offset each
+    /// other before scaling the move's accuracy. No roll is made when the
+    /// move cannot miss.
+    fn accuracy_check(&mut self, user: (usize, usize), target: (usize, usize), mv: &Move) -> bool {
+        let Some(accuracy) = mv.accuracy else {
+            return true;
+        };
+        let stage = self.stages[user.0][user.1].get(BattleStat::Accuracy)
+            - self.stages[target.0][target.1].get(BattleStat::Evasion);
+        let chance = (accuracy as f32 * StatStages::accuracy_multiplier(stage)) as u32;
+        chance >= 100 || self.rng.chance(chance, 100)
+    }
+
+    /// Strikes the creature in `target` once with `mv`
+    ///
+    /// Returns the HP the target lost.
+    fn deal_damage(
+        &mut self,
+        user: (usize, usize),
+        target: (usize, usize),
+        mv: &Move,
+        spread: bool,
+    ) -> u16 {
+        let attacker = self.active(user.0, user.1);
+        let defender = self.active(target.0, target.1);
+
+        let mut effective_power = mv.effective_power(&attacker.types, &defender.types);
+        if spread {
+            effective_power *= SPREAD_MODIFIER;
+        }
+        effective_power *= self.weather_power_multiplier(mv.move_type);
+        let level = attacker.level;
+        let source = attacker.id;
+        let target_id = defender.id;
+
+        let critical = effective_power > 0.0 && self.roll_critical(user, mv);
+        let mut damage = damage::calculate_damage(
+            level,
+            self.effective_attack(user, mv.category, critical),
+            self.effective_defense(target, mv.category, critical),
+            effective_power,
+        ) as f32;
+        if critical {
+            damage *= self.crit_multiplier;
+            self.events.push(BattleEvent::CriticalHit {
+                source,
+                target: target_id,
+            });
+        } else {
+            // Critical hits break through screens
+            damage *= self.screen_modifier(user, target, mv.category);
+        }
+
+        self.apply_damage(source, target, damage.min(u16::MAX as f32) as u16)
+    }
+
+    /// Whether the types involved leave `mv` from `user` unable to hurt `target`
+    fn is_immune_to(&self, user: (usize, usize), target: (usize, usize), mv: &Move) -> bool {
+        let attacker = self.active(user.0, user.1);
+        let defender = self.active(target.0, target.1);
+        mv.effective_power(&attacker.types, &defender.types) <= 0.0
+    }
+
+    /// Rolls whether a move used from `user` lands a critical hit
+    fn roll_critical(&mut self, (side, slot): (usize, usize), mv: &Move) -> bool {
+        let stage = mv.crit_stage.saturating_add(self.stages[side][slot].crit());
+        let (numerator, denominator) = critical::crit_chance(stage);
+        self.rng.chance(numerator, denominator)
+    }
+
+    /// Removes HP from the creature in `target` and reports the damage
+    ///
+    /// Returns the HP actually lost, which is capped by the remaining HP.
+    pub(super) fn apply_damage(
+        &mut self,
+        source: CreatureId,
+        (side, slot): (usize, usize),
+        damage: u16,
+    ) -> u16 {
+        let target = &mut self.parties[side].all_mut()[slot];
+        let target_id = target.id;
+        let before = target.current_hp;
+        target.modify_hp(-(damage.min(i16::MAX as u16) as i16));
+        let dealt = before - target.current_hp;
+        let fainted = target.is_fainted();
+
+        self.events.push(BattleEvent::Damage {
+            source,
+            target: target_id,
+            amount: dealt,
+        });
+        if fainted {
+            self.events.push(BattleEvent::Fainted {
+                creature: target_id,
+            });
+        }
+        dealt
+    }
+
+    /// Restores HP to the creature in `target` and reports the healing
+    ///
+    /// Returns the HP actually restored, which is capped by the missing HP.
+    pub(super) fn heal(
+        &mut self,
+        source: CreatureId,
+        (side, slot): (usize, usize),
+        amount: u16,
+    ) -> u16 {
+        let target = &mut self.parties[side].all_mut()[slot];
+        let before = target.current_hp;
+        target.modify_hp(amount.min(i16::MAX as u16) as i16);
+        let healed = target.current_hp - before;
+
+        self.events.push(BattleEvent::Heal {
+            source,
+            target: target.id,
+            amount: healed,
+        });
+        healed
+    }
+}
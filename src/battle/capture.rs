@@ -0,0 +1,108 @@
+use super::Battle;
+use super::format::BattleKind;
+use super::outcome::BattleResult;
+use crate::event::BattleEvent;
+use crate::moves::MoveRegistry;
+
+/// Catch values at or above this always catch
+const GUARANTEED_CATCH: u32 = 255;
+/// Checks a ball makes before the catch succeeds; all but the last show as shakes
+const SHAKE_CHECKS: u8 = 4;
+/// Shake rolls are measured out of this many
+const SHAKE_ROLL_SCALE: u32 = 65536;
+
+/// Modified catch rate of a creature, at least 1
+///
+/// A creature on its last HP is about three times as easy to catch as one at
+/// full health; the ball and status modifiers scale the result.
+pub fn catch_value(
+    current_hp: u16,
+    max_hp: u16,
+    catch_rate: u8,
+    ball_modifier: f32,
+    status_modifier: f32,
+) -> u32 {
+    let max_hp = max_hp.max(1) as f32;
+    let hp_factor = (3.0 * max_hp - 2.0 * current_hp as f32) / (3.0 * max_hp);
+    let value = hp_factor * catch_rate as f32 * ball_modifier * status_modifier;
+    value.max(1.0) as u32
+}
+
+/// Roll out of 65536 that each shake check must land under
+pub fn shake_threshold(catch_value: u32) -> u32 {
+    if catch_value >= GUARANTEED_CATCH {
+        return SHAKE_ROLL_SCALE;
+    }
+    let ratio = 16_711_680.0 / catch_value.max(1) as f64;
+    (1_048_560.0 / ratio.sqrt().sqrt()) as u32
+}
+
+impl<M: MoveRegistry> Battle<'_, M> {
+    /// Throws a ball at the wild creature, ending the battle if it is caught
+    ///
+    /// Each passed shake check before the last is reported as a shake.
+    pub(super) fn resolve_capture(&mut self, ball_modifier: f32) {
+        let BattleKind::Wild { catch_rate } = self.kind else {
+            return;
+        };
+        let wild = self.active(1, 0);
+        if wild.is_fainted() {
+            return;
+        }
+        let creature = wild.id;
+        let value = catch_value(
+            wild.current_hp,
+            wild.individual_stats.max_hp.get(),
+            catch_rate,
+            ball_modifier,
+            wild.status.map_or(1.0, |s| s.catch_multiplier()),
+        );
+        let threshold = shake_threshold(value);
+
+        for check in 1..=SHAKE_CHECKS {
+            let passed =
+                threshold >= SHAKE_ROLL_SCALE || self.rng.chance(threshold, SHAKE_ROLL_SCALE);
+            if !passed {
+                self.events.push(BattleEvent::BrokeFree { creature });
+                return;
+            }
+            if check < SHAKE_CHECKS {
+                self.events.push(BattleEvent::BallShook {
+                    creature,
+                    shakes: check,
+                });
+            }
+        }
+        self.events.push(BattleEvent::Captured { creature });
+        self.finish(BattleResult::Captured { creature });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn weakened_creatures_are_easier_to_catch() {
+        // Full HP leaves a third of the catch rate
+        assert_eq!(catch_value(30, 30, 45, 1.0, 1.0), 15);
+        assert_eq!(catch_value(1, 30, 45, 1.0, 1.0), 44);
+    }
+
+    #[test]
+    fn ball_and_status_scale_the_catch_value() {
+        assert_eq!(catch_value(30, 30, 45, 2.0, 1.5), 45);
+    }
+
+    #[test]
+    fn catch_value_is_at_least_one() {
+        assert_eq!(catch_value(30, 30, 1, 1.0, 1.0), 1);
+    }
+
+    #[test]
+    fn shake_threshold_rises_to_a_sure_catch() {
+        assert_eq!(shake_threshold(255), SHAKE_ROLL_SCALE);
+        assert_eq!(shake_threshold(254), 65_470);
+        assert!(shake_threshold(15) < shake_threshold(44));
+    }
+}
@@ -0,0 +1,32 @@
+/// Damage multiplier for critical hits unless the battle configures another
+pub const DEFAULT_CRIT_MULTIPLIER: f32 = 1.5;
+/// Highest critical-hit stage; anything above behaves the same
+pub const MAX_CRIT_STAGE: u8 = 3;
+
+/// Chance of a critical hit at the given stage, as `(numerator, denominator)`
+pub fn crit_chance(stage: u8) -> (u32, u32) {
+    match stage.min(MAX_CRIT_STAGE) {
+        0 => (1, 24),
+        1 => (1, 8),
+        2 => (1, 2),
+        _ => (1, 1),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chance_rises_with_stage() {
+        assert_eq!(crit_chance(0), (1, 24));
+        assert_eq!(crit_chance(1), (1, 8));
+        assert_eq!(crit_chance(2), (1, 2));
+        assert_eq!(crit_chance(3), (1, 1));
+    }
+
+    #[test]
+    fn stages_above_max_always_crit() {
+        assert_eq!(crit_chance(10), (1, 1));
+    }
+}
@@ -0,0 +1,43 @@
+use crate::experience::Level;
+
+/// Calculates the damage dealt by an attack
+///
+/// `effective_power` is expected to already include type effectiveness and STAB
+/// (see `Move::effective_power`), so an immune matchup yields 0 damage.
+pub fn calculate_damage(level: Level, attack: u16, defense: u16, effective_power: f32) -> u16 {
+    if effective_power <= 0.0 {
+        return 0;
+    }
+
+    let level_factor = (2 * level.get() as u32) / 5 + 2;
+    let base = level_factor as f32 * effective_power * attack as f32 / defense.max(1) as f32;
+    let damage = base / 50.0 + 2.0;
+    damage.min(u16::MAX as f32) as u16
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn level(n: u8) -> Level {
+        Level::new(n).unwrap()
+    }
+
+    #[test]
+    fn damage_scales_with_power() {
+        let weak = calculate_damage(level(50), 100, 100, 40.0);
+        let strong = calculate_damage(level(50), 100, 100, 80.0);
+        assert!(strong > weak);
+    }
+
+    #[test]
+    fn damage_matches_formula() {
+        // level factor = 2 * 50 / 5 + 2 = 22 -> 22 * 80 * 1 / 50 + 2 = 37.2
+        assert_eq!(calculate_damage(level(50), 100, 100, 80.0), 37);
+    }
+
+    #[test]
+    fn immune_matchup_deals_no_damage() {
+        assert_eq!(calculate_damage(level(50), 100, 100, 0.0), 0);
+    }
+}
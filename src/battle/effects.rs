@@ -0,0 +1,84 @@
+use super::Battle;
+use super::weather::DEFAULT_WEATHER_TURNS;
+use crate::moves::{EffectTarget, Move, MoveEffect, MoveRegistry, SecondaryEffect};
+
+/// The side of the field a field effect used from `user` lands on
+fn field_side(user: (usize, usize), target: EffectTarget) -> usize {
+    match target {
+        EffectTarget::User => user.0,
+        EffectTarget::Target => 1 - user.0,
+    }
+}
+
+impl<M: MoveRegistry> Battle<'_, M> {
+    /// Applies a move's secondary effects after its damage has been dealt
+    ///
+    /// Effects on the target roll and apply separately for each creature in
+    /// `landed`; every other effect rolls once for the whole use.
+    pub(super) fn apply_move_effects(
+        &mut self,
+        user: (usize, usize),
+        mv: &Move,
+        landed: &[(usize, usize)],
+    ) {
+        for secondary in &mv.effects {
+            if secondary.effect.targets_each_hit() {
+                for &target in landed.iter().filter(|&&pos| pos != user) {
+                    if !self.active(target.0, target.1).is_fainted() && self.roll_effect(secondary)
+                    {
+                        self.apply_move_effect(user, target, secondary.effect);
+                    }
+                }
+            } else if !self.active(user.0, user.1).is_fainted() && self.roll_effect(secondary) {
+                self.apply_move_effect(user, user, secondary.effect);
+            }
+        }
+    }
+
+    fn roll_effect(&mut self, secondary: &SecondaryEffect) -> bool {
+        match secondary.chance {
+            Some(percent) if percent < 100 => self.rng.chance(percent as u32, 100),
+            _ => true,
+        }
+    }
+
+    /// Applies one effect of a move used from `user`
+    ///
+    /// `target` is the creature hit for per-target effects and the user
+    /// otherwise.
+    fn apply_move_effect(
+        &mut self,
+        user: (usize, usize),
+        target: (usize, usize),
+        effect: MoveEffect,
+    ) {
+        match effect {
+            MoveEffect::Status { status, .. } => {
+                self.apply_status(target, status);
+            }
+            MoveEffect::StatChange { stat, change, .. } => {
+                self.apply_stat_change(target, stat, change);
+            }
+            MoveEffect::Volatile { condition, .. } => {
+                self.apply_volatile(target, condition, user);
+            }
+            MoveEffect::Weather(weather) => {
+                self.start_weather(weather, Some(DEFAULT_WEATHER_TURNS));
+            }
+            MoveEffect::SideCondition { target, condition } => {
+                self.start_side_condition(field_side(user, target), condition);
+            }
+            MoveEffect::RemoveSideCondition { target, condition } => {
+                self.end_side_condition(field_side(user, target), condition);
+            }
+            MoveEffect::ClearHazards { target } => {
+                self.remove_hazards(field_side(user, target));
+            }
+            MoveEffect::Heal(share) => {
+                let creature = self.active(user.0, user.1);
+                let amount = share.of(creature.individual_stats.max_hp.get()).max(1);
+                self.heal(creature.id, user, amount);
+            }
+        }
+    }
+}
@@ -0,0 +1,42 @@
+use crate::creature::CreatureId;
+use crate::item::ItemId;
+use crate::moves::MoveId;
+
+/// Reasons a choice made for a battle can be rejected
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ActionError {
+    /// The battle is not in a state where this choice can be made
+    WrongState,
+    /// The side has no fainted creature that needs replacing
+    NoReplacementNeeded { side: usize },
+    /// The creature is not part of the given side
+    NotOnSide { creature: CreatureId, side: usize },
+    /// The creature is already fighting
+    AlreadyActive { creature: CreatureId },
+    /// The creature has fainted and cannot be sent in
+    Fainted { creature: CreatureId },
+    /// The creature is not on the field to act
+    NotActive { creature: CreatureId },
+    /// The creature does not know the move
+    MoveNotKnown {
+        creature: CreatureId,
+        move_id: MoveId,
+    },
+    /// The move has no PP left
+    NoPp {
+        creature: CreatureId,
+        move_id: MoveId,
+    },
+    /// The creature already has an action queued this turn
+    AlreadyChosen { creature: CreatureId },
+    /// Struggle is only allowed once every move is out of PP
+    HasUsableMoves { creature: CreatureId },
+    /// Fleeing and catching are only possible in wild battles
+    TrainerBattle,
+    /// Only the player's side can flee or throw balls in a wild battle
+    NotPlayerSide,
+    /// The creature is trapped and cannot flee or switch out
+    Trapped { creature: CreatureId },
+    /// The item cannot be thrown to catch a creature
+    NotABall { item: ItemId },
+}
@@ -0,0 +1,102 @@
+use super::Battle;
+use super::error::ActionError;
+use super::format::BattleKind;
+use super::outcome::BattleResult;
+use super::volatile::Volatile;
+use crate::creature::CreatureId;
+use crate::event::BattleEvent;
+use crate::moves::MoveRegistry;
+
+/// Odds of escaping are measured out of this many
+const ESCAPE_ODDS_SCALE: u32 = 256;
+/// Extra escape odds gained for every attempt
+const ODDS_PER_ATTEMPT: u32 = 30;
+
+/// Escape odds out of 256 for a runner against the fastest foe
+///
+/// A runner at least as fast as the foe always escapes; otherwise the odds
+/// follow the speed ratio and grow with every attempt, this one included.
+pub fn escape_odds(runner_speed: u16, foe_speed: u16, attempts: u8) -> u32 {
+    if runner_speed >= foe_speed {
+        return ESCAPE_ODDS_SCALE;
+    }
+    let ratio = runner_speed as u32 * 128 / foe_speed.max(1) as u32;
+    (ratio + ODDS_PER_ATTEMPT * attempts as u32).min(ESCAPE_ODDS_SCALE)
+}
+
+impl<M: MoveRegistry> Battle<'_, M> {
+    /// Checks that `side` may take wild-only actions like fleeing or catching
+    pub(super) fn check_wild_action(&self, side: usize) -> Result<(), ActionError> {
+        if self.kind == BattleKind::Trainer {
+            return Err(ActionError::TrainerBattle);
+        }
+        // The wild creature is always on side 1
+        if side != 0 {
+            return Err(ActionError::NotPlayerSide);
+        }
+        Ok(())
+    }
+
+    /// Checks whether the creature in `pos` is allowed to try to flee
+    pub(super) fn can_flee(&self, (side, slot): (usize, usize)) -> Result<(), ActionError> {
+        self.check_wild_action(side)?;
+        if self.volatiles[side][slot].has(Volatile::Trapped) {
+            return Err(ActionError::Trapped {
+                creature: self.active(side, slot).id,
+            });
+        }
+        Ok(())
+    }
+
+    /// Rolls an escape attempt, ending the battle if it succeeds
+    pub(super) fn resolve_flee(&mut self, creature_id: CreatureId) {
+        let Some((side, slot)) = self.locate_active(creature_id) else {
+            return;
+        };
+        if self.active(side, slot).is_fainted() || self.can_flee((side, slot)).is_err() {
+            return;
+        }
+
+        self.flee_attempts[side] = self.flee_attempts[side].saturating_add(1);
+        let foe_speed = (0..self.active_count(1 - side))
+            .filter(|&foe_slot| !self.active(1 - side, foe_slot).is_fainted())
+            .map(|foe_slot| self.effective_speed((1 - side, foe_slot)))
+            .max()
+            .unwrap_or(0);
+        let odds = escape_odds(
+            self.effective_speed((side, slot)),
+            foe_speed,
+            self.flee_attempts[side],
+        );
+
+        if odds >= ESCAPE_ODDS_SCALE || self.rng.chance(odds, ESCAPE_ODDS_SCALE) {
+            self.events.push(BattleEvent::Fled {
+                creature: creature_id,
+            });
+            self.finish(BattleResult::Fled { side });
+        } else {
+            self.events.push(BattleEvent::FleeFailed {
+                creature: creature_id,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn faster_runner_always_escapes() {
+        assert_eq!(escape_odds(20, 10, 1), 256);
+        assert_eq!(escape_odds(10, 10, 1), 256);
+    }
+
+    #[test]
+    fn odds_grow_with_attempts() {
+        // 5 * 128 / 10 = 64, plus 30 per attempt
+        assert_eq!(escape_odds(5, 10, 1), 94);
+        assert_eq!(escape_odds(5, 10, 2), 124);
+        assert_eq!(escape_odds(5, 10, 10), 256);
+    }
+}
@@ -0,0 +1,30 @@
+/// How many creatures each side fields at once
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BattleFormat {
+    #[default]
+    Single,
+    Double,
+}
+
+/// Who the player is up against, which decides whether running is allowed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BattleKind {
+    /// Against a wild creature; side 0 may try to flee or catch it
+    Wild {
+        /// Catch rate of the wild creature's species
+        catch_rate: u8,
+    },
+    /// Against another trainer; nobody can flee
+    #[default]
+    Trainer,
+}
+
+impl BattleFormat {
+    /// Number of active slots per side
+    pub fn active_per_side(self) -> usize {
+        match self {
+            BattleFormat::Single => 1,
+            BattleFormat::Double => 2,
+        }
+    }
+}
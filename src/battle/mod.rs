@@ -1,9 +1,47 @@
+pub mod ai;
+mod attack;
+mod capture;
+pub mod critical;
+pub mod damage;
+mod effects;
+pub mod error;
+mod flee;
+pub mod format;
+pub mod ordering;
+pub mod outcome;
+pub mod replay;
+pub mod rng;
+pub mod search;
+pub mod side;
+pub mod stages;
+mod status;
+mod timed;
+pub mod view;
+pub mod volatile;
+pub mod weather;
+
 #[cfg(test)]
 mod tests;
 
-use crate::creature::CreatureId;
+use crate::creature::{Creature, CreatureId};
 use crate::encounter::Encounter;
+use crate::event::BattleEvent;
+use crate::event_queue::EventQueue;
+use crate::moves::{Move, MoveId, MoveRegistry};
 use crate::party::Party;
+use crate::status::StatusCondition;
+use error::ActionError;
+use format::{BattleFormat, BattleKind};
+use ordering::{ActionBracket, ActionOrder};
+use outcome::{BattleOutcome, BattleResult};
+use replay::{BattleRecord, RecordedIntervention, Replacement, TurnRecord};
+use rng::{BattleRng, SplitMix64};
+use side::SideState;
+use stages::StatStages;
+use std::rc::Rc;
+use view::Revealed;
+use volatile::{Volatile, VolatileSet};
+use weather::ActiveWeather;
 
 /// Represents the phases of a battle turn
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -12,11 +50,13 @@ pub enum BattleState {
     SelectActions,
     ResolveActions,
     EndTurn,
+    /// A side must pick a benched creature to replace a fainted one
+    ReplaceFainted,
     Finished,
 }
 
 /// Represents a single turn and the actions selected for it
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Turn {
     pub turn_number: u32,
     pub actions: Vec<BattleAction>,
@@ -41,6 +81,12 @@ pub enum BattleAction {
     Attack {
         attacker_id: CreatureId,
         target_id: CreatureId,
+        move_id: MoveId,
+    },
+    /// Fallback attack for a creature with no usable moves left
+    Struggle {
+        attacker_id: CreatureId,
+        target_id: CreatureId,
     },
     Switch {
         out_id: CreatureId,
@@ -50,23 +96,148 @@ pub enum BattleAction {
         user_id: CreatureId,
         item_id: u32,
     },
+    /// Throw a ball at the wild creature
+    Capture {
+        thrower_id: CreatureId,
+        /// Catch chance multiplier of the ball
+        ball_modifier: f32,
+    },
+    /// Try to run away from a wild battle
+    Flee {
+        creature_id: CreatureId,
+    },
     Pass,
 }
 
+impl BattleAction {
+    /// The creature taking the action, if any
+    pub fn actor(&self) -> Option<CreatureId> {
+        match self {
+            BattleAction::Attack { attacker_id, .. }
+            | BattleAction::Struggle { attacker_id, .. } => Some(*attacker_id),
+            BattleAction::Switch { out_id, .. } => Some(*out_id),
+            BattleAction::UseItem { user_id, .. } => Some(*user_id),
+            BattleAction::Capture { thrower_id, .. } => Some(*thrower_id),
+            BattleAction::Flee { creature_id } => Some(*creature_id),
+            BattleAction::Pass => None,
+        }
+    }
+}
+
 /// The Battle struct itself, managing parties and turn state
-pub struct Battle {
+pub struct Battle<'a, M: MoveRegistry> {
     pub parties: [Party; 2],
     pub state: BattleState,
     pub current_turn: Turn,
+    pub events: EventQueue,
+    format: BattleFormat,
+    kind: BattleKind,
+    /// Volatile conditions per side and active slot
+    volatiles: [Vec<VolatileSet>; 2],
+    /// Stat stages per side and active slot
+    stages: [Vec<StatStages>; 2],
+    weather: Option<ActiveWeather>,
+    sides: [SideState; 2],
+    crit_multiplier: f32,
+    /// Escape attempts made by each side so far
+    flee_attempts: [u8; 2],
+    /// What each side has shown the other of its team
+    revealed: [Revealed; 2],
+    moves: &'a M,
+    rng: Box<dyn BattleRng>,
+    seed: Option<u64>,
+    outcome: Option<BattleOutcome>,
+    initial_parties: Rc<[Party; 2]>,
+    history: Vec<TurnRecord>,
+    interventions: Vec<RecordedIntervention>,
+    forfeited_by: Option<usize>,
 }
 
-impl Battle {
-    pub fn new(party1: Party, party2: Party) -> Self {
-        Self {
+impl<'a, M: MoveRegistry> Battle<'a, M> {
+    /// Creates a battle whose randomness is fully determined by `seed`
+    pub fn new(
+        party1: Party,
+        party2: Party,
+        moves: &'a M,
+        format: BattleFormat,
+        seed: u64,
+    ) -> Self {
+        let rng = Box::new(SplitMix64::new(seed));
+        let mut battle = Self::with_rng(party1, party2, moves, format, rng);
+        battle.seed = Some(seed);
+        battle
+    }
+
+    /// Creates a battle drawing from a custom RNG, e.g. a scripted one in tests
+    pub fn with_rng(
+        mut party1: Party,
+        mut party2: Party,
+        moves: &'a M,
+        format: BattleFormat,
+        rng: Box<dyn BattleRng>,
+    ) -> Self {
+        let active_per_side = format.active_per_side();
+        Self::send_out_leads(&mut party1, active_per_side);
+        Self::send_out_leads(&mut party2, active_per_side);
+        let mut battle = Self {
+            initial_parties: Rc::new([party1.clone(), party2.clone()]),
+            history: Vec::new(),
+            interventions: Vec::new(),
+            forfeited_by: None,
             parties: [party1, party2],
             state: BattleState::StartTurn,
             current_turn: Turn::new(1),
+            events: EventQueue::new(),
+            format,
+            kind: BattleKind::default(),
+            volatiles: std::array::from_fn(|_| vec![VolatileSet::default(); active_per_side]),
+            stages: std::array::from_fn(|_| vec![StatStages::default(); active_per_side]),
+            weather: None,
+            sides: Default::default(),
+            crit_multiplier: critical::DEFAULT_CRIT_MULTIPLIER,
+            flee_attempts: [0; 2],
+            revealed: Default::default(),
+            moves,
+            rng,
+            seed: None,
+            outcome: None,
+        };
+        for side in 0..2 {
+            for slot in 0..battle.active_count(side) {
+                battle.reveal((side, slot));
+            }
         }
+        battle
+    }
+
+    /// The seed the battle RNG was created from
+    ///
+    /// Returns `None` for battles built with a custom RNG via [`Battle::with_rng`].
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+
+    /// Hands back both parties once the battle is done with them
+    ///
+    /// Damage, status and spent PP stay on the creatures.
+    pub fn into_parties(self) -> [Party; 2] {
+        self.parties
+    }
+
+    /// Marks the battle as wild or against a trainer
+    ///
+    /// Battles are against a trainer unless set otherwise.
+    pub fn set_kind(&mut self, kind: BattleKind) {
+        self.kind = kind;
+    }
+
+    pub fn kind(&self) -> BattleKind {
+        self.kind
+    }
+
+    /// Sets the damage multiplier used for critical hits
+    pub fn set_crit_multiplier(&mut self, multiplier: f32) {
+        self.crit_multiplier = multiplier;
     }
 
     /// Advance to the next state in the turn cycle
@@ -75,22 +246,462 @@ impl Battle {
             BattleState::StartTurn => BattleState::SelectActions,
             BattleState::SelectActions => BattleState::ResolveActions,
             BattleState::ResolveActions => BattleState::EndTurn,
-            BattleState::EndTurn => {
-                self.current_turn = Turn::new(self.current_turn.turn_number + 1);
-                BattleState::StartTurn
+            BattleState::EndTurn | BattleState::ReplaceFainted => {
+                if self.pending_replacements().is_empty() {
+                    self.current_turn = Turn::new(self.current_turn.turn_number + 1);
+                    BattleState::StartTurn
+                } else {
+                    BattleState::ReplaceFainted
+                }
             }
             BattleState::Finished => BattleState::Finished,
         };
     }
+
+    /// Everything needed to reproduce this battle with [`BattleRecord::replay`]
+    ///
+    /// Returns `None` for battles built with a custom RNG, which have no seed.
+    pub fn record(&self) -> Option<BattleRecord> {
+        Some(BattleRecord {
+            parties: (*self.initial_parties).clone(),
+            format: self.format,
+            kind: self.kind,
+            crit_multiplier: self.crit_multiplier,
+            seed: self.seed?,
+            turns: self.history.clone(),
+            interventions: self.interventions.clone(),
+            forfeited_by: self.forfeited_by,
+        })
+    }
+
+    /// A copy of the battle to play choices out on without touching this one
+    ///
+    /// The copy draws from its own RNG seeded with `seed`, so it gives away
+    /// nothing about this battle's upcoming rolls. Its events are dropped and
+    /// it keeps no history, so it cannot be recorded.
+    pub fn simulate(&self, seed: u64) -> Self {
+        Self {
+            parties: self.parties.clone(),
+            state: self.state,
+            current_turn: self.current_turn.clone(),
+            events: EventQueue::muted(),
+            format: self.format,
+            kind: self.kind,
+            volatiles: self.volatiles.clone(),
+            stages: self.stages.clone(),
+            weather: self.weather,
+            sides: self.sides.clone(),
+            crit_multiplier: self.crit_multiplier,
+            flee_attempts: self.flee_attempts,
+            revealed: self.revealed.clone(),
+            moves: self.moves,
+            rng: Box::new(SplitMix64::new(seed)),
+            seed: None,
+            outcome: self.outcome,
+            initial_parties: Rc::clone(&self.initial_parties),
+            history: Vec::new(),
+            interventions: Vec::new(),
+            forfeited_by: self.forfeited_by,
+        }
+    }
+
+    /// The format this battle is played in
+    pub fn format(&self) -> BattleFormat {
+        self.format
+    }
+
+    /// How many active slots `side` fills
+    ///
+    /// A party smaller than the format fights with fewer creatures on the field.
+    fn active_count(&self, side: usize) -> usize {
+        self.format
+            .active_per_side()
+            .min(self.parties[side].all().len())
+    }
+
+    /// Fills the active slots at the front of the party with creatures able to fight
+    fn send_out_leads(party: &mut Party, active_per_side: usize) {
+        for slot in 0..active_per_side.min(party.all().len()) {
+            if let Some(idx) = party.all()[slot..].iter().position(|c| !c.is_fainted()) {
+                party.swap(slot, slot + idx);
+            }
+        }
+    }
+
+    /// The creature fighting in active `slot` of `side`
+    ///
+    /// Active creatures always occupy the first slots of their party.
+    pub fn active(&self, side: usize, slot: usize) -> &Creature {
+        debug_assert!(slot < self.active_count(side));
+        &self.parties[side].all()[slot]
+    }
+
+    /// All creatures currently in an active slot of `side`, fainted or not
+    pub fn actives(&self, side: usize) -> &[Creature] {
+        &self.parties[side].all()[..self.active_count(side)]
+    }
+
+    /// Sides with a fainted active creature and a benched creature to send in
+    pub fn pending_replacements(&self) -> Vec<usize> {
+        (0..self.parties.len())
+            .filter(|&side| {
+                let (actives, bench) = self.parties[side].all().split_at(self.active_count(side));
+                actives.iter().any(|c| c.is_fainted()) && bench.iter().any(|c| !c.is_fainted())
+            })
+            .collect()
+    }
+
+    /// Sends in `in_id` to replace a fainted active creature of `side`
+    ///
+    /// In double battles the first fainted slot is filled.
+    pub fn replace_fainted(&mut self, side: usize, in_id: CreatureId) -> Result<(), ActionError> {
+        if self.state != BattleState::ReplaceFainted {
+            return Err(ActionError::WrongState);
+        }
+        if !self.pending_replacements().contains(&side) {
+            return Err(ActionError::NoReplacementNeeded { side });
+        }
+        let bench_slot = self.bench_slot(side, in_id)?;
+        let active_slot = self
+            .actives(side)
+            .iter()
+            .position(|c| c.is_fainted())
+            .expect("pending replacement has a fainted slot");
+
+        let out_id = self.active(side, active_slot).id;
+        self.switch_in(side, active_slot, bench_slot, out_id);
+        if let Some(turn) = self.history.last_mut() {
+            turn.replacements.push(Replacement {
+                side,
+                creature: in_id,
+            });
+        }
+        // Entry hazards may knock out the last creature able to fight
+        self.check_for_end();
+        Ok(())
+    }
+
+    /// Moves the creature in an active slot still has PP for
+    pub fn usable_moves(&self, side: usize, slot: usize) -> Vec<MoveId> {
+        self.active(side, slot)
+            .moves
+            .iter()
+            .flatten()
+            .filter(|m| m.pp.current > 0)
+            .map(|m| m.move_id.clone())
+            .collect()
+    }
+
+    /// Checks an action against the rules and queues it for this turn
+    ///
+    /// Attacks must use a known move with PP left, and Struggle is only
+    /// allowed once no such move remains. Each creature gets one action a turn.
+    pub fn submit_action(&mut self, action: BattleAction) -> Result<(), ActionError> {
+        if self.state != BattleState::SelectActions {
+            return Err(ActionError::WrongState);
+        }
+        if let Some(creature) = action.actor()
+            && self
+                .current_turn
+                .actions
+                .iter()
+                .any(|queued| queued.actor() == Some(creature))
+        {
+            return Err(ActionError::AlreadyChosen { creature });
+        }
+        match &action {
+            BattleAction::Attack {
+                attacker_id,
+                move_id,
+                ..
+            } => {
+                let (side, slot) = self.acting_slot(*attacker_id)?;
+                let known = self
+                    .active(side, slot)
+                    .moves
+                    .iter()
+                    .flatten()
+                    .find(|m| m.move_id == *move_id)
+                    .ok_or_else(|| ActionError::MoveNotKnown {
+                        creature: *attacker_id,
+                        move_id: move_id.clone(),
+                    })?;
+                if known.pp.current == 0 {
+                    return Err(ActionError::NoPp {
+                        creature: *attacker_id,
+                        move_id: move_id.clone(),
+                    });
+                }
+            }
+            BattleAction::Struggle { attacker_id, .. } => {
+                let (side, slot) = self.acting_slot(*attacker_id)?;
+                if !self.usable_moves(side, slot).is_empty() {
+                    return Err(ActionError::HasUsableMoves {
+                        creature: *attacker_id,
+                    });
+                }
+            }
+            BattleAction::Switch { out_id, in_id } => {
+                let (side, slot) = self.acting_slot(*out_id)?;
+                self.bench_slot(side, *in_id)?;
+                if self.volatiles[side][slot].has(Volatile::Trapped) {
+                    return Err(ActionError::Trapped { creature: *out_id });
+                }
+            }
+            BattleAction::Flee { creature_id } => {
+                let pos = self.acting_slot(*creature_id)?;
+                self.can_flee(pos)?;
+            }
+            BattleAction::Capture { thrower_id, .. } => {
+                let (side, _) = self.acting_slot(*thrower_id)?;
+                self.check_wild_action(side)?;
+            }
+            BattleAction::UseItem { .. } | BattleAction::Pass => {}
+        }
+        self.current_turn.add_action(action);
+        Ok(())
+    }
+
+    /// Finds the slot of an active creature that is able to act
+    fn acting_slot(&self, id: CreatureId) -> Result<(usize, usize), ActionError> {
+        let pos = self
+            .locate_active(id)
+            .ok_or(ActionError::NotActive { creature: id })?;
+        if self.active(pos.0, pos.1).is_fainted() {
+            return Err(ActionError::Fainted { creature: id });
+        }
+        Ok(pos)
+    }
+
+    /// Validates that `id` is a benched creature of `side` able to fight
+    fn bench_slot(&self, side: usize, id: CreatureId) -> Result<usize, ActionError> {
+        match self.locate(id) {
+            Some((s, slot)) if s == side && slot < self.active_count(side) => {
+                Err(ActionError::AlreadyActive { creature: id })
+            }
+            Some((s, slot)) if s == side => {
+                if self.parties[side].all()[slot].is_fainted() {
+                    Err(ActionError::Fainted { creature: id })
+                } else {
+                    Ok(slot)
+                }
+            }
+            _ => Err(ActionError::NotOnSide { creature: id, side }),
+        }
+    }
+
+    fn switch_in(
+        &mut self,
+        side: usize,
+        active_slot: usize,
+        bench_slot: usize,
+        out_id: CreatureId,
+    ) {
+        // The toxic counter restarts once the creature leaves the field
+        let leaving = &mut self.parties[side].all_mut()[active_slot];
+        if let Some(StatusCondition::BadlyPoisoned { .. }) = leaving.status {
+            leaving.status = Some(StatusCondition::BadlyPoisoned { turns: 1 });
+        }
+        self.clear_volatiles((side, active_slot));
+        self.stages[side][active_slot] = StatStages::default();
+        self.parties[side].swap(active_slot, bench_slot);
+        self.reveal((side, active_slot));
+        self.events.push(BattleEvent::SwitchedIn {
+            creature: self.active(side, active_slot).id,
+            replacing: out_id,
+        });
+        self.apply_entry_hazards((side, active_slot));
+    }
+
+    /// Finds the active slot a creature is fighting in, if any
+    fn locate_active(&self, id: CreatureId) -> Option<(usize, usize)> {
+        self.locate(id)
+            .filter(|&(side, slot)| slot < self.active_count(side))
+    }
+
+    /// The result of the battle, once it has finished
+    pub fn outcome(&self) -> Option<BattleOutcome> {
+        self.outcome
+    }
+
+    /// Ends the battle with `side` giving up, handing the win to the other side
+    pub fn forfeit(&mut self, side: usize) {
+        if self.state == BattleState::Finished {
+            return;
+        }
+        self.forfeited_by = Some(side);
+        self.finish(BattleResult::Forfeit { winner: 1 - side });
+    }
+
+    fn finish(&mut self, result: BattleResult) {
+        self.outcome = Some(BattleOutcome::new(result, self.current_turn.turn_number));
+        self.state = BattleState::Finished;
+    }
+
+    /// Finishes the battle if either side has no creatures left able to fight
+    ///
+    /// Returns `true` if the battle is now over.
+    fn check_for_end(&mut self) -> bool {
+        let result = match (self.parties[0].all_fainted(), self.parties[1].all_fainted()) {
+            (true, true) => BattleResult::Draw,
+            (true, false) => BattleResult::Won { winner: 1 },
+            (false, true) => BattleResult::Won { winner: 0 },
+            (false, false) => return false,
+        };
+        self.finish(result);
+        true
+    }
+
+    /// Finds the side and party slot of a creature taking part in the battle
+    fn locate(&self, id: CreatureId) -> Option<(usize, usize)> {
+        self.parties.iter().enumerate().find_map(|(side, party)| {
+            party
+                .all()
+                .iter()
+                .position(|c| c.id == id)
+                .map(|slot| (side, slot))
+        })
+    }
+
+    /// Computes the sort key used to decide when an action resolves
+    fn action_order(&mut self, action: &BattleAction) -> ActionOrder {
+        let speed_of = |battle: &Self, id: &CreatureId| {
+            battle
+                .locate_active(*id)
+                .map_or(0, |pos| battle.effective_speed(pos))
+        };
+
+        let (bracket, priority, speed) = match action {
+            BattleAction::Switch { out_id, .. } => {
+                (ActionBracket::Switch, 0, speed_of(self, out_id))
+            }
+            BattleAction::UseItem { user_id, .. }
+            | BattleAction::Capture {
+                thrower_id: user_id,
+                ..
+            } => (ActionBracket::Item, 0, speed_of(self, user_id)),
+            BattleAction::Attack {
+                attacker_id,
+                move_id,
+                ..
+            } => {
+                let priority = self.moves.get(move_id).map_or(0, |m| m.priority);
+                (ActionBracket::Attack, priority, speed_of(self, attacker_id))
+            }
+            BattleAction::Struggle { attacker_id, .. } => {
+                (ActionBracket::Attack, 0, speed_of(self, attacker_id))
+            }
+            BattleAction::Flee { creature_id } => {
+                (ActionBracket::Flee, 0, speed_of(self, creature_id))
+            }
+            BattleAction::Pass => (ActionBracket::Pass, 0, 0),
+        };
+
+        ActionOrder {
+            bracket,
+            priority,
+            speed,
+            tie_breaker: self.rng.next_u64(),
+        }
+    }
+
+    /// Sorts the current turn's actions into resolution order
+    fn order_actions(&mut self) {
+        let actions = std::mem::take(&mut self.current_turn.actions);
+        let mut keyed: Vec<_> = actions
+            .into_iter()
+            .map(|action| (self.action_order(&action), action))
+            .collect();
+        keyed.sort_by_key(|(order, _)| *order);
+        self.current_turn.actions = keyed.into_iter().map(|(_, action)| action).collect();
+    }
+
+    /// Resolve every action selected for the current turn
+    fn resolve_actions(&mut self) {
+        self.history.push(TurnRecord::new(
+            self.current_turn.turn_number,
+            self.current_turn.actions.clone(),
+        ));
+        self.order_actions();
+        let actions = std::mem::take(&mut self.current_turn.actions);
+        // Attacks aim at a slot, so they hit whoever occupies it when they land
+        let targets: Vec<_> = actions
+            .iter()
+            .map(|action| match action {
+                BattleAction::Attack { target_id, .. }
+                | BattleAction::Struggle { target_id, .. } => self.locate_active(*target_id),
+                _ => None,
+            })
+            .collect();
+        for (action, target) in actions.iter().zip(targets) {
+            if self.is_over() {
+                break;
+            }
+            self.resolve_action(action, target);
+        }
+        self.current_turn.actions = actions;
+    }
+
+    fn resolve_action(&mut self, action: &BattleAction, target: Option<(usize, usize)>) {
+        match action {
+            BattleAction::Attack {
+                attacker_id,
+                move_id,
+                ..
+            } => {
+                if let Some(mv) = self.moves.get(move_id) {
+                    self.resolve_attack(*attacker_id, target, mv);
+                }
+            }
+            BattleAction::Struggle { attacker_id, .. } => {
+                self.resolve_attack(*attacker_id, target, &Move::struggle());
+            }
+            BattleAction::Switch { out_id, in_id } => self.resolve_switch(*out_id, *in_id),
+            BattleAction::Flee { creature_id } => self.resolve_flee(*creature_id),
+            BattleAction::Capture { ball_modifier, .. } => self.resolve_capture(*ball_modifier),
+            // Placeholder: items are not resolved yet
+            BattleAction::UseItem { .. } | BattleAction::Pass => {}
+        }
+    }
+
+    fn resolve_switch(&mut self, out_id: CreatureId, in_id: CreatureId) {
+        let Some((side, active_slot)) = self.locate_active(out_id) else {
+            return;
+        };
+        if self.active(side, active_slot).is_fainted() {
+            return;
+        }
+        if let Ok(bench_slot) = self.bench_slot(side, in_id) {
+            self.switch_in(side, active_slot, bench_slot, out_id);
+        }
+    }
 }
 
-impl Encounter for Battle {
+impl<M: MoveRegistry> Encounter for Battle<'_, M> {
     fn process_turn(&mut self) {
         if self.state == BattleState::Finished {
             return;
         }
 
-        // Placeholder: future logic to collect and execute actions
+        match self.state {
+            BattleState::ResolveActions => {
+                self.resolve_actions();
+                if self.check_for_end() {
+                    return;
+                }
+            }
+            BattleState::EndTurn => {
+                self.weather_damage();
+                self.status_end_of_turn();
+                self.volatiles_end_of_turn();
+                self.sides_end_of_turn();
+                self.weather_end_of_turn();
+                if self.check_for_end() {
+                    return;
+                }
+            }
+            _ => {}
+        }
         self.advance_state();
     }
 
@@ -0,0 +1,78 @@
+use std::cmp::Ordering;
+
+/// Sort key deciding when an action resolves within a turn
+///
+/// Actions are ordered by bracket (fleeing, switches and items before attacks),
+/// then by move priority, then by the acting creature's speed, and finally
+/// by a seeded tie-breaker so that equal actions still resolve reproducibly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ActionOrder {
+    pub bracket: ActionBracket,
+    pub priority: i8,
+    pub speed: u16,
+    pub tie_breaker: u64,
+}
+
+/// Coarse grouping of actions; earlier variants always act first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ActionBracket {
+    Flee,
+    Switch,
+    Item,
+    Attack,
+    Pass,
+}
+
+impl Ord for ActionOrder {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.bracket
+            .cmp(&other.bracket)
+            .then_with(|| other.priority.cmp(&self.priority))
+            .then_with(|| other.speed.cmp(&self.speed))
+            .then_with(|| other.tie_breaker.cmp(&self.tie_breaker))
+    }
+}
+
+impl PartialOrd for ActionOrder {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn order(bracket: ActionBracket, priority: i8, speed: u16, tie_breaker: u64) -> ActionOrder {
+        ActionOrder {
+            bracket,
+            priority,
+            speed,
+            tie_breaker,
+        }
+    }
+
+    #[test]
+    fn bracket_beats_priority_and_speed() {
+        let switch = order(ActionBracket::Switch, 0, 1, 0);
+        let attack = order(ActionBracket::Attack, 5, 500, 0);
+        assert!(switch < attack);
+    }
+
+    #[test]
+    fn priority_beats_speed() {
+        let quick = order(ActionBracket::Attack, 1, 10, 0);
+        let fast = order(ActionBracket::Attack, 0, 500, 0);
+        assert!(quick < fast);
+    }
+
+    #[test]
+    fn faster_acts_first_then_tie_breaker() {
+        let fast = order(ActionBracket::Attack, 0, 100, 0);
+        let slow = order(ActionBracket::Attack, 0, 50, 9);
+        assert!(fast < slow);
+
+        let lucky = order(ActionBracket::Attack, 0, 50, 10);
+        assert!(lucky < slow);
+    }
+}
@@ -0,0 +1,64 @@
+use crate::creature::CreatureId;
+
+/// How a finished battle was decided
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BattleResult {
+    /// The other side ran out of creatures able to fight
+    Won { winner: usize },
+    /// Both sides ran out of creatures at the same time
+    Draw,
+    /// The losing side gave up
+    Forfeit { winner: usize },
+    /// The side ran away from a wild battle
+    Fled { side: usize },
+    /// The wild creature was caught
+    Captured { creature: CreatureId },
+}
+
+/// Final result of a battle, available once it has finished
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BattleOutcome {
+    pub result: BattleResult,
+    /// Turn during which the battle ended
+    pub turns: u32,
+}
+
+impl BattleOutcome {
+    pub fn new(result: BattleResult, turns: u32) -> Self {
+        Self { result, turns }
+    }
+
+    /// Index of the winning side, if there is one
+    pub fn winner(&self) -> Option<usize> {
+        match self.result {
+            BattleResult::Won { winner } | BattleResult::Forfeit { winner } => Some(winner),
+            BattleResult::Draw | BattleResult::Fled { .. } | BattleResult::Captured { .. } => None,
+        }
+    }
+
+    pub fn is_draw(&self) -> bool {
+        self.result == BattleResult::Draw
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn winner_is_reported_for_wins_and_forfeits() {
+        let won = BattleOutcome::new(BattleResult::Won { winner: 1 }, 3);
+        assert_eq!(won.winner(), Some(1));
+
+        let forfeit = BattleOutcome::new(BattleResult::Forfeit { winner: 0 }, 1);
+        assert_eq!(forfeit.winner(), Some(0));
+        assert!(!forfeit.is_draw());
+    }
+
+    #[test]
+    fn draw_has_no_winner() {
+        let draw = BattleOutcome::new(BattleResult::Draw, 5);
+        assert_eq!(draw.winner(), None);
+        assert!(draw.is_draw());
+    }
+}
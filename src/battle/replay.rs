@@ -0,0 +1,336 @@
+use super::format::{BattleFormat, BattleKind};
+use super::side::SideCondition;
+use super::stages::BattleStat;
+use super::volatile::Volatile;
+use super::weather::Weather;
+use super::{Battle, BattleAction, BattleState};
+use crate::creature::CreatureId;
+use crate::encounter::Encounter;
+use crate::moves::MoveRegistry;
+use crate::party::Party;
+use crate::status::StatusKind;
+use std::iter::Peekable;
+use std::slice;
+
+/// A replacement chosen for a fainted creature during `BattleState::ReplaceFainted`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Replacement {
+    pub side: usize,
+    pub creature: CreatureId,
+}
+
+/// Everything chosen by the players during a single turn
+#[derive(Debug, Clone)]
+pub struct TurnRecord {
+    pub turn_number: u32,
+    /// Actions in the order they were submitted, before any sorting
+    pub actions: Vec<BattleAction>,
+    pub replacements: Vec<Replacement>,
+}
+
+impl TurnRecord {
+    pub fn new(turn_number: u32, actions: Vec<BattleAction>) -> Self {
+        Self {
+            turn_number,
+            actions,
+            replacements: Vec::new(),
+        }
+    }
+}
+
+/// A change made to the battle from outside, through its public API rather
+/// than by a player's choice
+///
+/// Battles log these so a replay can make the same change at the same point.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Intervention {
+    SetWeather {
+        weather: Weather,
+        turns: Option<u8>,
+    },
+    AddSideCondition {
+        side: usize,
+        condition: SideCondition,
+    },
+    RemoveSideCondition {
+        side: usize,
+        condition: SideCondition,
+    },
+    ClearHazards {
+        side: usize,
+    },
+    InflictStatus {
+        target: (usize, usize),
+        kind: StatusKind,
+    },
+    AddVolatile {
+        target: (usize, usize),
+        condition: Volatile,
+        source: (usize, usize),
+    },
+    ChangeStat {
+        target: (usize, usize),
+        stat: BattleStat,
+        delta: i8,
+    },
+    RaiseCritStage {
+        target: (usize, usize),
+        amount: u8,
+    },
+}
+
+/// An intervention along with when in the battle it was made
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RecordedIntervention {
+    pub turn_number: u32,
+    pub state: BattleState,
+    pub intervention: Intervention,
+}
+
+/// All inputs needed to reproduce a battle exactly
+#[derive(Debug, Clone)]
+pub struct BattleRecord {
+    pub parties: [Party; 2],
+    pub format: BattleFormat,
+    pub kind: BattleKind,
+    /// Damage multiplier used for critical hits
+    pub crit_multiplier: f32,
+    pub seed: u64,
+    pub turns: Vec<TurnRecord>,
+    /// Changes made from outside, in the order they were made
+    pub interventions: Vec<RecordedIntervention>,
+    /// Side that gave up, if the battle ended by forfeit
+    pub forfeited_by: Option<usize>,
+}
+
+impl BattleRecord {
+    /// Plays the recorded choices back through a fresh battle
+    ///
+    /// The returned battle's event queue holds every event of the original
+    /// battle, in the same order. Interventions are made again just before
+    /// the battle leaves the turn and state they were recorded in; those made
+    /// while fainted creatures were being replaced come before the replacements.
+    pub fn replay<'a, M: MoveRegistry>(&self, moves: &'a M) -> Battle<'a, M> {
+        let [party1, party2] = self.parties.clone();
+        let mut battle = Battle::new(party1, party2, moves, self.format, self.seed);
+        battle.set_kind(self.kind);
+        battle.set_crit_multiplier(self.crit_multiplier);
+        let mut pending = self.interventions.iter().peekable();
+
+        for turn in &self.turns {
+            while battle.state != BattleState::SelectActions && !battle.is_over() {
+                battle.intervene_due(&mut pending);
+                battle.process_turn();
+            }
+            for action in &turn.actions {
+                battle.current_turn.add_action(action.clone());
+            }
+            // Resolve the turn and stop once it hands over to the next one
+            while !matches!(
+                battle.state,
+                BattleState::StartTurn | BattleState::ReplaceFainted | BattleState::Finished
+            ) {
+                battle.intervene_due(&mut pending);
+                battle.process_turn();
+            }
+            battle.intervene_due(&mut pending);
+            for replacement in &turn.replacements {
+                let _ = battle.replace_fainted(replacement.side, replacement.creature);
+            }
+        }
+        // Catch up on changes made during the turn still being chosen
+        while pending.peek().is_some() && !battle.is_over() {
+            battle.intervene_due(&mut pending);
+            if battle.state == BattleState::SelectActions {
+                break;
+            }
+            battle.process_turn();
+        }
+
+        if let Some(side) = self.forfeited_by {
+            battle.forfeit(side);
+        }
+        battle
+    }
+}
+
+impl<M: MoveRegistry> Battle<'_, M> {
+    /// Notes a change made from outside so it ends up in the battle's record
+    pub(super) fn log_intervention(&mut self, intervention: Intervention) {
+        self.interventions.push(RecordedIntervention {
+            turn_number: self.current_turn.turn_number,
+            state: self.state,
+            intervention,
+        });
+    }
+
+    /// Makes a recorded change again through the public API
+    fn intervene(&mut self, intervention: Intervention) {
+        match intervention {
+            Intervention::SetWeather { weather, turns } => {
+                self.set_weather(weather, turns);
+            }
+            Intervention::AddSideCondition { side, condition } => {
+                self.add_side_condition(side, condition);
+            }
+            Intervention::RemoveSideCondition { side, condition } => {
+                self.remove_side_condition(side, condition);
+            }
+            Intervention::ClearHazards { side } => self.clear_hazards(side),
+            Intervention::InflictStatus { target, kind } => {
+                self.inflict_status(target, kind);
+            }
+            Intervention::AddVolatile {
+                target,
+                condition,
+                source,
+            } => {
+                self.add_volatile(target, condition, source);
+            }
+            Intervention::ChangeStat {
+                target,
+                stat,
+                delta,
+            } => {
+                self.change_stat(target, stat, delta);
+            }
+            Intervention::RaiseCritStage { target, amount } => {
+                self.raise_crit_stage(target, amount);
+            }
+        }
+    }
+
+    /// Makes every pending change that was recorded at the current turn and state
+    fn intervene_due(&mut self, pending: &mut Peekable<slice::Iter<'_, RecordedIntervention>>) {
+        while let Some(recorded) = pending
+            .next_if(|r| r.turn_number == self.current_turn.turn_number && r.state == self.state)
+        {
+            self.intervene(recorded.intervention);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::BattleEvent;
+    use crate::moves::MoveId;
+    use crate::tests::helpers::{MockMoveRegistry, full_party, start_selection};
+
+    /// Plays a full battle where both sides attack every turn and replace
+    /// fainted creatures with the first one available
+    fn play_out(battle: &mut Battle<'_, MockMoveRegistry>) -> Vec<BattleEvent> {
+        let mut events = Vec::new();
+        while !battle.is_over() {
+            match battle.state {
+                BattleState::SelectActions => {
+                    for side in 0..2 {
+                        battle.current_turn.add_action(BattleAction::Attack {
+                            attacker_id: battle.active(side, 0).id,
+                            target_id: battle.active(1 - side, 0).id,
+                            move_id: MoveId(side as u16 + 1),
+                        });
+                    }
+                }
+                BattleState::ReplaceFainted => {
+                    for side in battle.pending_replacements() {
+                        let next = battle.parties[side]
+                            .all()
+                            .iter()
+                            .find(|c| !c.is_fainted())
+                            .unwrap()
+                            .id;
+                        battle.replace_fainted(side, next).unwrap();
+                    }
+                }
+                _ => {}
+            }
+            battle.process_turn();
+            events.extend(battle.events.drain());
+        }
+        events
+    }
+
+    #[test]
+    fn replay_reproduces_every_event() {
+        let moves = MockMoveRegistry::new();
+        let mut battle = Battle::new(
+            full_party(),
+            full_party(),
+            &moves,
+            BattleFormat::Single,
+            1234,
+        );
+        let events = play_out(&mut battle);
+        assert!(events.len() > 10);
+
+        let record = battle.record().unwrap();
+        assert_eq!(record.seed, 1234);
+        assert_eq!(record.turns.len() as u32, battle.outcome().unwrap().turns);
+
+        let mut replayed = record.replay(&moves);
+        assert_eq!(replayed.events.drain(), events);
+        assert_eq!(replayed.outcome(), battle.outcome());
+    }
+
+    #[test]
+    fn replay_repeats_changes_made_from_outside() {
+        let moves = MockMoveRegistry::new();
+        let mut battle = Battle::new(full_party(), full_party(), &moves, BattleFormat::Single, 99);
+        battle.set_weather(Weather::Sandstorm, None);
+        battle.add_side_condition(1, SideCondition::Spikes);
+        start_selection(&mut battle);
+        battle.inflict_status((1, 0), StatusKind::Burn);
+        battle.add_volatile((0, 0), Volatile::Confusion, (1, 0));
+        battle.change_stat((0, 0), BattleStat::Attack, 2);
+        let mut events = battle.events.drain();
+        events.extend(play_out(&mut battle));
+
+        let record = battle.record().unwrap();
+        assert_eq!(record.interventions.len(), 5);
+        let mut replayed = record.replay(&moves);
+        assert_eq!(replayed.events.drain(), events);
+        assert_eq!(replayed.outcome(), battle.outcome());
+    }
+
+    #[test]
+    fn replay_keeps_the_crit_multiplier() {
+        let moves = MockMoveRegistry::new();
+        let mut battle = Battle::new(full_party(), full_party(), &moves, BattleFormat::Single, 7);
+        battle.set_crit_multiplier(3.0);
+        let events = play_out(&mut battle);
+        assert!(
+            events
+                .iter()
+                .any(|e| matches!(e, BattleEvent::CriticalHit { .. }))
+        );
+
+        let record = battle.record().unwrap();
+        assert_eq!(record.crit_multiplier, 3.0);
+        let mut replayed = record.replay(&moves);
+        assert_eq!(replayed.events.drain(), events);
+    }
+
+    #[test]
+    fn replay_reproduces_forfeit() {
+        let moves = MockMoveRegistry::new();
+        let mut battle = Battle::new(full_party(), full_party(), &moves, BattleFormat::Single, 1);
+        battle.forfeit(0);
+
+        let replayed = battle.record().unwrap().replay(&moves);
+        assert_eq!(replayed.outcome(), battle.outcome());
+    }
+
+    #[test]
+    fn battles_with_custom_rng_cannot_be_recorded() {
+        let moves = MockMoveRegistry::new();
+        let battle = Battle::with_rng(
+            full_party(),
+            full_party(),
+            &moves,
+            BattleFormat::Single,
+            Box::new(crate::battle::rng::ScriptedRng::always(0)),
+        );
+        assert!(battle.record().is_none());
+    }
+}
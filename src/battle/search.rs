@@ -0,0 +1,361 @@
+use super::ai::{BattleAi, GreedyAi, SwitchingAi};
+use super::rng::{BattleRng, SplitMix64};
+use super::view::{BattleView, OpponentCreature};
+use super::{Battle, BattleAction, BattleState};
+use crate::creature::{Creature, CreatureId};
+use crate::encounter::Encounter;
+use crate::moves::{MoveRegistry, MoveTarget};
+use std::time::{Duration, Instant};
+
+/// Weight of the exploration term when picking which choice to play out next
+const EXPLORATION: f32 = std::f32::consts::SQRT_2;
+
+/// Limits on the work [`SearchAi`] does for each decision
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SearchBudget {
+    /// Most playouts to run
+    pub iterations: u32,
+    /// Turns each playout runs before the position is scored
+    pub depth: u32,
+    /// Wall-clock limit, checked between playouts
+    pub time_limit: Option<Duration>,
+}
+
+impl Default for SearchBudget {
+    fn default() -> Self {
+        Self {
+            iterations: 200,
+            depth: 3,
+            time_limit: None,
+        }
+    }
+}
+
+/// Plays each candidate choice out on copies of the battle and keeps the best
+///
+/// The root of a Monte Carlo tree search: choices are picked to play out by
+/// UCB1, every later decision in a playout is made by [`GreedyAi`] for both
+/// sides, and the choice played out most often wins. Playouts draw from
+/// their own RNG, so the search cannot see the real battle's rolls coming.
+pub struct SearchAi {
+    budget: SearchBudget,
+    rng: SplitMix64,
+}
+
+impl SearchAi {
+    pub fn new(seed: u64, budget: SearchBudget) -> Self {
+        Self {
+            budget,
+            rng: SplitMix64::new(seed),
+        }
+    }
+
+    /// Plays `choice` out once and scores where the battle ends up
+    fn playout<M: MoveRegistry>(
+        &mut self,
+        view: &BattleView<'_, M>,
+        choice: &[BattleAction],
+    ) -> f32 {
+        let side = view.side();
+        let mut sim = view.simulate(self.rng.next_u64());
+        let stop_turn = sim.current_turn.turn_number + self.budget.depth.max(1);
+        for action in choice {
+            // Choices are built from the view, so they are always legal
+            let _ = sim.submit_action(action.clone());
+        }
+        let _ = sim.submit_ai_choices(view.opponent(), &mut GreedyAi);
+        sim.process_turn();
+
+        while !sim.is_over() {
+            match sim.state {
+                BattleState::SelectActions if sim.current_turn.turn_number >= stop_turn => break,
+                BattleState::SelectActions | BattleState::ReplaceFainted => {
+                    for side in 0..2 {
+                        let _ = sim.submit_ai_choices(side, &mut GreedyAi);
+                    }
+                }
+                _ => {}
+            }
+            sim.process_turn();
+        }
+        score(&sim, side)
+    }
+}
+
+impl<M: MoveRegistry> BattleAi<M> for SearchAi {
+    fn choose_actions(&mut self, view: &BattleView<'_, M>) -> Vec<BattleAction> {
+        let choices = candidate_choices(view);
+        if choices.len() <= 1 {
+            return choices.into_iter().next().unwrap_or_default();
+        }
+
+        let started = Instant::now();
+        // Playouts and total score per choice
+        let mut results = vec![(0u32, 0.0f32); choices.len()];
+        for played in 0..self.budget.iterations {
+            if self
+                .budget
+                .time_limit
+                .is_some_and(|limit| started.elapsed() >= limit)
+            {
+                break;
+            }
+            let pick = results
+                .iter()
+                .position(|&(visits, _)| visits == 0)
+                .unwrap_or_else(|| {
+                    let log_total = (played as f32).ln();
+                    let ucb = |&(visits, total): &(u32, f32)| {
+                        total / visits as f32 + EXPLORATION * (log_total / visits as f32).sqrt()
+                    };
+                    (0..results.len())
+                        .max_by(|&a, &b| ucb(&results[a]).total_cmp(&ucb(&results[b])))
+                        .expect("there are at least two choices")
+                });
+            let reward = self.playout(view, &choices[pick]);
+            results[pick].0 += 1;
+            results[pick].1 += reward;
+        }
+
+        let mean = |(visits, total): (u32, f32)| total / visits.max(1) as f32;
+        let best = (0..choices.len())
+            .max_by(|&a, &b| {
+                results[a]
+                    .0
+                    .cmp(&results[b].0)
+                    .then(mean(results[a]).total_cmp(&mean(results[b])))
+            })
+            .expect("there are at least two choices");
+        choices.into_iter().nth(best).unwrap_or_default()
+    }
+
+    fn choose_replacement(&mut self, view: &BattleView<'_, M>) -> CreatureId {
+        SwitchingAi.choose_replacement(view)
+    }
+}
+
+/// Every combination of actions the side's active creatures could take
+///
+/// Combinations sending the same benched creature into two slots are left out.
+fn candidate_choices<M: MoveRegistry>(view: &BattleView<'_, M>) -> Vec<Vec<BattleAction>> {
+    let foes: Vec<OpponentCreature> = view
+        .opponent_actives()
+        .into_iter()
+        .filter(|c| !c.is_fainted())
+        .collect();
+    let mut choices = vec![Vec::new()];
+    for (slot, creature) in view.actives().iter().enumerate() {
+        if creature.is_fainted() {
+            continue;
+        }
+        let options = slot_options(view, slot, creature, &foes);
+        choices = choices
+            .into_iter()
+            .flat_map(|choice| {
+                options.iter().filter_map(move |option| {
+                    let repeated = switched_in(option).is_some_and(|id| {
+                        choice.iter().any(|taken| switched_in(taken) == Some(id))
+                    });
+                    (!repeated).then(|| {
+                        let mut extended = choice.clone();
+                        extended.push(option.clone());
+                        extended
+                    })
+                })
+            })
+            .collect();
+    }
+    choices
+}
+
+/// The creature an action sends in, if it is a switch
+fn switched_in(action: &BattleAction) -> Option<CreatureId> {
+    match action {
+        BattleAction::Switch { in_id, .. } => Some(*in_id),
+        _ => None,
+    }
+}
+
+/// Actions the creature in active `slot` could take this turn
+fn slot_options<M: MoveRegistry>(
+    view: &BattleView<'_, M>,
+    slot: usize,
+    creature: &Creature,
+    foes: &[OpponentCreature],
+) -> Vec<BattleAction> {
+    let mut options = Vec::new();
+    let usable = view.usable_moves(slot);
+    for move_id in &usable {
+        // Only single-target moves care which foe was picked
+        let single = view
+            .move_data(move_id)
+            .is_some_and(|mv| mv.target == MoveTarget::Single);
+        let targets = if single {
+            foes
+        } else {
+            &foes[..foes.len().min(1)]
+        };
+        options.extend(targets.iter().map(|target| BattleAction::Attack {
+            attacker_id: creature.id,
+            target_id: target.id,
+            move_id: move_id.clone(),
+        }));
+    }
+    if usable.is_empty() {
+        options.extend(foes.iter().map(|target| BattleAction::Struggle {
+            attacker_id: creature.id,
+            target_id: target.id,
+        }));
+    }
+    if view.can_switch(slot) {
+        options.extend(view.bench().iter().filter(|c| !c.is_fainted()).map(|c| {
+            BattleAction::Switch {
+                out_id: creature.id,
+                in_id: c.id,
+            }
+        }));
+    }
+    if options.is_empty() {
+        // Nothing left to attack and nobody to switch to
+        options.push(BattleAction::Pass);
+    }
+    options
+}
+
+/// How good the battle looks for `side`, from 0 (lost) to 1 (won)
+///
+/// Unfinished battles are scored by the share of HP each side has left.
+fn score<M: MoveRegistry>(battle: &Battle<'_, M>, side: usize) -> f32 {
+    if let Some(outcome) = battle.outcome() {
+        return match outcome.winner() {
+            Some(winner) if winner == side => 1.0,
+            Some(_) => 0.0,
+            None => 0.5,
+        };
+    }
+    let hp_share = |side: usize| {
+        let (current, max) =
+            battle.parties[side]
+                .all()
+                .iter()
+                .fold((0u32, 0u32), |(current, max), c| {
+                    (
+                        current + c.current_hp as u32,
+                        max + c.individual_stats.max_hp.get() as u32,
+                    )
+                });
+        current as f32 / max.max(1) as f32
+    };
+    0.5 + (hp_share(side) - hp_share(1 - side)) / 2.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::battle::format::BattleFormat;
+    use crate::creature_type::CreatureType::Normal;
+    use crate::moves::MoveId;
+    use crate::tests::helpers::{MockMoveRegistry, battle, creature};
+
+    /// A battle where our strongest move misses half the time but our
+    /// weakest is enough to finish off the foe
+    fn finishing_blow(moves: &MockMoveRegistry) -> Battle<'_, MockMoveRegistry> {
+        let mut foe = creature(&[Normal], &[2]);
+        foe.current_hp = 1;
+        let mut battle = battle(
+            moves,
+            BattleFormat::Single,
+            vec![creature(&[Normal], &[2, 3])],
+            vec![foe],
+        );
+        // The foe has shown it can hit back
+        battle.reveal_move((1, 0), &MoveId(2));
+        battle
+    }
+
+    fn shaky_grass_cut() -> MockMoveRegistry {
+        let mut moves = MockMoveRegistry::new();
+        moves.get_mut(&MoveId(3)).unwrap().accuracy = Some(50);
+        moves
+    }
+
+    fn chosen_move(actions: &[BattleAction]) -> MoveId {
+        match actions {
+            [BattleAction::Attack { move_id, .. }] => move_id.clone(),
+            other => panic!("expected one attack, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn search_prefers_a_sure_knockout_over_expected_damage() {
+        let moves = shaky_grass_cut();
+        let battle = finishing_blow(&moves);
+        let budget = SearchBudget {
+            iterations: 100,
+            depth: 1,
+            time_limit: None,
+        };
+
+        assert_eq!(
+            chosen_move(&GreedyAi.choose_actions(&battle.view(0))),
+            MoveId(3)
+        );
+        assert_eq!(
+            chosen_move(&SearchAi::new(4, budget).choose_actions(&battle.view(0))),
+            MoveId(2)
+        );
+    }
+
+    #[test]
+    fn search_leaves_the_battle_untouched() {
+        let moves = shaky_grass_cut();
+        let battle = finishing_blow(&moves);
+
+        SearchAi::new(4, SearchBudget::default()).choose_actions(&battle.view(0));
+
+        assert!(battle.events.is_empty());
+        assert!(battle.current_turn.actions.is_empty());
+        assert_eq!(battle.active(1, 0).current_hp, 1);
+        assert_eq!(battle.usable_moves(0, 0), vec![MoveId(2), MoveId(3)]);
+    }
+
+    #[test]
+    fn search_passes_when_no_foe_is_left_standing() {
+        let moves = shaky_grass_cut();
+        let mut battle = finishing_blow(&moves);
+        battle.parties[1].creatures[0].current_hp = 0;
+
+        let actions = SearchAi::new(4, SearchBudget::default()).choose_actions(&battle.view(0));
+        assert!(matches!(actions[..], [BattleAction::Pass]));
+    }
+
+    #[test]
+    fn search_still_answers_when_out_of_time() {
+        let moves = shaky_grass_cut();
+        let battle = finishing_blow(&moves);
+        let budget = SearchBudget {
+            time_limit: Some(Duration::ZERO),
+            ..SearchBudget::default()
+        };
+
+        let actions = SearchAi::new(4, budget).choose_actions(&battle.view(0));
+        assert_eq!(actions.len(), 1);
+    }
+
+    #[test]
+    fn double_battle_choices_never_send_in_the_same_creature_twice() {
+        let moves = MockMoveRegistry::new();
+        let water_gun = || creature(&[Normal], &[1]);
+        let battle = battle(
+            &moves,
+            BattleFormat::Double,
+            vec![water_gun(), water_gun(), water_gun()],
+            vec![water_gun(), water_gun()],
+        );
+
+        let choices = candidate_choices(&battle.view(0));
+        // Per slot: Water Gun at either foe, or switching to the one benched creature
+        assert_eq!(choices.len(), 3 * 3 - 1);
+        assert!(choices.iter().all(|choice| choice.len() == 2));
+    }
+}
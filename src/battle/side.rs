@@ -0,0 +1,258 @@
+use super::Battle;
+use super::replay::Intervention;
+use super::timed::{self, Timed};
+use crate::creature_type::CreatureType;
+use crate::event::BattleEvent;
+use crate::moves::{MoveCategory, MoveRegistry};
+pub use crate::side_condition::SideCondition;
+use crate::status::StatusKind;
+
+/// Damage multiplier applied by a screen
+pub const SCREEN_MODIFIER: f32 = 0.5;
+/// Speed multiplier applied by tailwind
+pub const TAILWIND_MODIFIER: f32 = 2.0;
+
+#[derive(Debug, Clone, Copy)]
+struct SideEntry {
+    condition: SideCondition,
+    layers: u8,
+    turns_left: Option<u8>,
+}
+
+impl Timed for SideEntry {
+    type Condition = SideCondition;
+
+    fn condition(&self) -> SideCondition {
+        self.condition
+    }
+
+    fn turns_left(&mut self) -> &mut Option<u8> {
+        &mut self.turns_left
+    }
+}
+
+/// The side conditions in effect for one side
+#[derive(Debug, Clone, Default)]
+pub struct SideState {
+    entries: Vec<SideEntry>,
+}
+
+impl SideState {
+    /// Adds the condition or one more layer of it
+    ///
+    /// Returns `false` if it is already present at its maximum layers.
+    pub fn add(&mut self, condition: SideCondition) -> bool {
+        if let Some(entry) = self.entries.iter_mut().find(|e| e.condition == condition) {
+            if entry.layers >= condition.max_layers() {
+                return false;
+            }
+            entry.layers += 1;
+            return true;
+        }
+        self.entries.push(SideEntry {
+            condition,
+            layers: 1,
+            turns_left: condition.duration(),
+        });
+        true
+    }
+
+    pub fn has(&self, condition: SideCondition) -> bool {
+        self.layers(condition) > 0
+    }
+
+    pub fn layers(&self, condition: SideCondition) -> u8 {
+        self.entries
+            .iter()
+            .find(|e| e.condition == condition)
+            .map_or(0, |e| e.layers)
+    }
+
+    pub fn remove(&mut self, condition: SideCondition) -> bool {
+        let before = self.entries.len();
+        self.entries.retain(|e| e.condition != condition);
+        self.entries.len() != before
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = SideCondition> + '_ {
+        self.entries.iter().map(|e| e.condition)
+    }
+
+    /// Counts down every timed condition, returning those that ran out
+    pub fn tick(&mut self) -> Vec<SideCondition> {
+        timed::tick(&mut self.entries)
+    }
+}
+
+impl<M: MoveRegistry> Battle<'_, M> {
+    /// The side conditions in effect for `side`
+    pub fn side_conditions(&self, side: usize) -> &SideState {
+        &self.sides[side]
+    }
+
+    /// Sets a condition on `side`, or adds a layer of a stackable hazard
+    pub fn add_side_condition(&mut self, side: usize, condition: SideCondition) -> bool {
+        self.log_intervention(Intervention::AddSideCondition { side, condition });
+        self.start_side_condition(side, condition)
+    }
+
+    /// Sets a side condition as part of the battle itself, e.g. from a move
+    pub(super) fn start_side_condition(&mut self, side: usize, condition: SideCondition) -> bool {
+        if !self.sides[side].add(condition) {
+            return false;
+        }
+        self.events
+            .push(BattleEvent::SideConditionStarted { side, condition });
+        true
+    }
+
+    /// Removes a condition from `side`
+    pub fn remove_side_condition(&mut self, side: usize, condition: SideCondition) -> bool {
+        self.log_intervention(Intervention::RemoveSideCondition { side, condition });
+        self.end_side_condition(side, condition)
+    }
+
+    /// Removes a side condition as part of the battle itself, e.g. from a move
+    pub(super) fn end_side_condition(&mut self, side: usize, condition: SideCondition) -> bool {
+        if !self.sides[side].remove(condition) {
+            return false;
+        }
+        self.events
+            .push(BattleEvent::SideConditionEnded { side, condition });
+        true
+    }
+
+    /// Removes every entry hazard from `side`
+    pub fn clear_hazards(&mut self, side: usize) {
+        self.log_intervention(Intervention::ClearHazards { side });
+        self.remove_hazards(side);
+    }
+
+    /// Removes every entry hazard from `side` as part of the battle itself
+    pub(super) fn remove_hazards(&mut self, side: usize) {
+        let hazards: Vec<_> = self.sides[side].iter().filter(|c| c.is_hazard()).collect();
+        for hazard in hazards {
+            self.end_side_condition(side, hazard);
+        }
+    }
+
+    /// Triggers the entry hazards of `side` on the creature that just switched into `slot`
+    pub(super) fn apply_entry_hazards(&mut self, (side, slot): (usize, usize)) {
+        let hazards: Vec<_> = self.sides[side]
+            .iter()
+            .filter(|c| c.is_hazard())
+            .map(|c| (c, self.sides[side].layers(c)))
+            .collect();
+
+        for (hazard, layers) in hazards {
+            let creature = self.active(side, slot);
+            if creature.is_fainted() {
+                return;
+            }
+            let id = creature.id;
+            let max_hp = creature.individual_stats.max_hp.get() as f32;
+            let rock_multiplier =
+                CreatureType::combined_multiplier(CreatureType::Rock, &creature.types);
+
+            self.events.push(BattleEvent::HazardTriggered {
+                creature: id,
+                hazard,
+            });
+            match hazard {
+                SideCondition::Spikes => {
+                    let fraction = match layers {
+                        1 => 1.0 / 8.0,
+                        2 => 1.0 / 6.0,
+                        _ => 1.0 / 4.0,
+                    };
+                    self.apply_damage(id, (side, slot), ((max_hp * fraction) as u16).max(1));
+                }
+                SideCondition::StealthRock => {
+                    let damage = (max_hp * rock_multiplier / 8.0) as u16;
+                    if damage > 0 {
+                        self.apply_damage(id, (side, slot), damage);
+                    }
+                }
+                SideCondition::ToxicSpikes => {
+                    let status = if layers >= 2 {
+                        StatusKind::BadlyPoisoned
+                    } else {
+                        StatusKind::Poison
+                    };
+                    self.apply_status((side, slot), status);
+                }
+                SideCondition::Reflect | SideCondition::LightScreen | SideCondition::Tailwind => {}
+            }
+        }
+    }
+
+    /// Damage multiplier from screens protecting the creature in `target`
+    /// against a move of `category`
+    pub(super) fn screen_modifier(
+        &self,
+        user: (usize, usize),
+        target: (usize, usize),
+        category: MoveCategory,
+    ) -> f32 {
+        let screen = match category {
+            MoveCategory::Physical => SideCondition::Reflect,
+            MoveCategory::Special => SideCondition::LightScreen,
+            MoveCategory::Status => return 1.0,
+        };
+        if user.0 != target.0 && self.sides[target.0].has(screen) {
+            SCREEN_MODIFIER
+        } else {
+            1.0
+        }
+    }
+
+    /// Speed multiplier from tailwind on `side`
+    pub(super) fn tailwind_modifier(&self, side: usize) -> f32 {
+        if self.sides[side].has(SideCondition::Tailwind) {
+            TAILWIND_MODIFIER
+        } else {
+            1.0
+        }
+    }
+
+    /// Counts down screens and tailwind on both sides
+    pub(super) fn sides_end_of_turn(&mut self) {
+        for side in 0..self.sides.len() {
+            for condition in self.sides[side].tick() {
+                self.events
+                    .push(BattleEvent::SideConditionEnded { side, condition });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hazards_stack_up_to_their_layer_limit() {
+        let mut side = SideState::default();
+        for _ in 0..3 {
+            assert!(side.add(SideCondition::Spikes));
+        }
+        assert!(!side.add(SideCondition::Spikes));
+        assert_eq!(side.layers(SideCondition::Spikes), 3);
+
+        assert!(side.add(SideCondition::StealthRock));
+        assert!(!side.add(SideCondition::StealthRock));
+    }
+
+    #[test]
+    fn timed_conditions_expire() {
+        let mut side = SideState::default();
+        side.add(SideCondition::Tailwind);
+        side.add(SideCondition::Spikes);
+
+        for _ in 0..3 {
+            assert!(side.tick().is_empty());
+        }
+        assert_eq!(side.tick(), vec![SideCondition::Tailwind]);
+        assert!(side.has(SideCondition::Spikes));
+    }
+}
@@ -0,0 +1,248 @@
+use super::Battle;
+use super::critical::MAX_CRIT_STAGE;
+use super::replay::Intervention;
+pub use crate::battle_stat::BattleStat;
+use crate::event::BattleEvent;
+use crate::moves::{MoveCategory, MoveRegistry};
+
+/// Temporary stat modifiers of an active creature, each between -6 and +6
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StatStages {
+    attack: i8,
+    defense: i8,
+    special_attack: i8,
+    special_defense: i8,
+    speed: i8,
+    accuracy: i8,
+    evasion: i8,
+    /// Critical-hit stages gained from effects like focusing or held items
+    crit: u8,
+}
+
+impl StatStages {
+    pub const MIN: i8 = -6;
+    pub const MAX: i8 = 6;
+
+    pub fn get(&self, stat: BattleStat) -> i8 {
+        match stat {
+            BattleStat::Attack => self.attack,
+            BattleStat::Defense => self.defense,
+            BattleStat::SpecialAttack => self.special_attack,
+            BattleStat::SpecialDefense => self.special_defense,
+            BattleStat::Speed => self.speed,
+            BattleStat::Accuracy => self.accuracy,
+            BattleStat::Evasion => self.evasion,
+        }
+    }
+
+    fn get_mut(&mut self, stat: BattleStat) -> &mut i8 {
+        match stat {
+            BattleStat::Attack => &mut self.attack,
+            BattleStat::Defense => &mut self.defense,
+            BattleStat::SpecialAttack => &mut self.special_attack,
+            BattleStat::SpecialDefense => &mut self.special_defense,
+            BattleStat::Speed => &mut self.speed,
+            BattleStat::Accuracy => &mut self.accuracy,
+            BattleStat::Evasion => &mut self.evasion,
+        }
+    }
+
+    /// Moves a stage by `delta`, clamped to the allowed range
+    ///
+    /// Returns the change actually applied.
+    pub fn change(&mut self, stat: BattleStat, delta: i8) -> i8 {
+        let stage = self.get_mut(stat);
+        let before = *stage;
+        *stage = before.saturating_add(delta).clamp(Self::MIN, Self::MAX);
+        *stage - before
+    }
+
+    pub fn crit(&self) -> u8 {
+        self.crit
+    }
+
+    /// Raises the critical-hit stage, capped at the highest stage
+    ///
+    /// Returns the increase actually applied.
+    pub fn raise_crit(&mut self, amount: u8) -> u8 {
+        let before = self.crit;
+        self.crit = before.saturating_add(amount).min(MAX_CRIT_STAGE);
+        self.crit - before
+    }
+
+    /// Multiplier for attack, defense and speed at the given stage
+    pub fn multiplier(stage: i8) -> f32 {
+        let stage = stage.clamp(Self::MIN, Self::MAX) as f32;
+        if stage >= 0.0 {
+            (2.0 + stage) / 2.0
+        } else {
+            2.0 / (2.0 - stage)
+        }
+    }
+
+    /// Multiplier for accuracy and evasion at the given stage
+    pub fn accuracy_multiplier(stage: i8) -> f32 {
+        let stage = stage.clamp(Self::MIN, Self::MAX) as f32;
+        if stage >= 0.0 {
+            (3.0 + stage) / 3.0
+        } else {
+            3.0 / (3.0 - stage)
+        }
+    }
+}
+
+impl<M: MoveRegistry> Battle<'_, M> {
+    /// The stat stages of the creature in an active slot
+    pub fn stages(&self, side: usize, slot: usize) -> &StatStages {
+        &self.stages[side][slot]
+    }
+
+    /// Raises or lowers a stat stage of the creature in `target`
+    ///
+    /// Returns the change actually applied; 0 means the stat was already at its limit.
+    pub fn change_stat(&mut self, target: (usize, usize), stat: BattleStat, delta: i8) -> i8 {
+        self.log_intervention(Intervention::ChangeStat {
+            target,
+            stat,
+            delta,
+        });
+        self.apply_stat_change(target, stat, delta)
+    }
+
+    /// Changes a stat stage as part of the battle itself, e.g. from a move
+    pub(super) fn apply_stat_change(
+        &mut self,
+        (side, slot): (usize, usize),
+        stat: BattleStat,
+        delta: i8,
+    ) -> i8 {
+        let creature = self.active(side, slot);
+        if creature.is_fainted() || delta == 0 {
+            return 0;
+        }
+        let id = creature.id;
+
+        let change = self.stages[side][slot].change(stat, delta);
+        if change == 0 {
+            self.events.push(BattleEvent::StatLimitReached {
+                creature: id,
+                stat,
+                raising: delta > 0,
+            });
+        } else {
+            self.events.push(BattleEvent::StatChanged {
+                creature: id,
+                stat,
+                change,
+            });
+        }
+        change
+    }
+
+    /// Raises the critical-hit stage of the creature in `pos`, as focusing or
+    /// crit-boosting items and abilities do
+    ///
+    /// Returns the increase actually applied, which is capped at the highest stage.
+    pub fn raise_crit_stage(&mut self, (side, slot): (usize, usize), amount: u8) -> u8 {
+        self.log_intervention(Intervention::RaiseCritStage {
+            target: (side, slot),
+            amount,
+        });
+        self.stages[side][slot].raise_crit(amount)
+    }
+
+    /// Attacking stat the creature in `pos` uses for a move of `category`,
+    /// with its stage and status applied
+    ///
+    /// Special moves use special attack and are not weakened by a burn.
+    /// Critical hits ignore a lowered stage.
+    pub(super) fn effective_attack(
+        &self,
+        (side, slot): (usize, usize),
+        category: MoveCategory,
+        critical: bool,
+    ) -> u16 {
+        let creature = self.active(side, slot);
+        let stats = &creature.individual_stats;
+        let (base, stat, status_multiplier) = match category {
+            MoveCategory::Special => (stats.special_attack, BattleStat::SpecialAttack, 1.0),
+            _ => (
+                stats.attack,
+                BattleStat::Attack,
+                creature.status.map_or(1.0, |s| s.attack_multiplier()),
+            ),
+        };
+        let mut stage = self.stages[side][slot].get(stat);
+        if critical {
+            stage = stage.max(0);
+        }
+        (base.get() as f32 * StatStages::multiplier(stage) * status_multiplier) as u16
+    }
+
+    /// Defending stat the creature in `pos` uses against a move of
+    /// `category`, with its stage applied
+    ///
+    /// Critical hits ignore a raised stage.
+    pub(super) fn effective_defense(
+        &self,
+        (side, slot): (usize, usize),
+        category: MoveCategory,
+        critical: bool,
+    ) -> u16 {
+        let stats = &self.active(side, slot).individual_stats;
+        let (base, stat) = match category {
+            MoveCategory::Special => (stats.special_defense, BattleStat::SpecialDefense),
+            _ => (stats.defense, BattleStat::Defense),
+        };
+        let mut stage = self.stages[side][slot].get(stat);
+        if critical {
+            stage = stage.min(0);
+        }
+        (base.get() as f32 * StatStages::multiplier(stage)) as u16
+    }
+
+    /// Speed of the creature in `pos` with its stage, status and tailwind applied
+    pub(super) fn effective_speed(&self, (side, slot): (usize, usize)) -> u16 {
+        let creature = self.active(side, slot);
+        let stage = self.stages[side][slot].get(BattleStat::Speed);
+        let speed = creature.individual_stats.speed.get() as f32
+            * StatStages::multiplier(stage)
+            * creature.status.map_or(1.0, |s| s.speed_multiplier())
+            * self.tailwind_modifier(side);
+        speed as u16
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stages_clamp_at_six() {
+        let mut stages = StatStages::default();
+        assert_eq!(stages.change(BattleStat::Attack, 4), 4);
+        assert_eq!(stages.change(BattleStat::Attack, 4), 2);
+        assert_eq!(stages.change(BattleStat::Attack, 1), 0);
+        assert_eq!(stages.get(BattleStat::Attack), 6);
+
+        assert_eq!(stages.change(BattleStat::Evasion, -12), -6);
+        assert_eq!(stages.get(BattleStat::Evasion), StatStages::MIN);
+    }
+
+    #[test]
+    fn stage_multipliers() {
+        assert_eq!(StatStages::multiplier(0), 1.0);
+        assert_eq!(StatStages::multiplier(2), 2.0);
+        assert_eq!(StatStages::multiplier(6), 4.0);
+        assert_eq!(StatStages::multiplier(-2), 0.5);
+        assert_eq!(StatStages::multiplier(-6), 0.25);
+    }
+
+    #[test]
+    fn accuracy_stage_multipliers() {
+        assert_eq!(StatStages::accuracy_multiplier(0), 1.0);
+        assert_eq!(StatStages::accuracy_multiplier(3), 2.0);
+        assert_eq!(StatStages::accuracy_multiplier(-3), 0.5);
+        assert_eq!(StatStages::accuracy_multiplier(6), 3.0);
+    }
+}
@@ -0,0 +1,122 @@
+use super::Battle;
+use super::replay::Intervention;
+use crate::event::BattleEvent;
+use crate::moves::MoveRegistry;
+use crate::status::{StatusCondition, StatusKind};
+
+/// Chance out of 4 that a paralysed creature cannot move
+const FULL_PARALYSIS_CHANCE: u32 = 1;
+/// Chance out of 5 that a frozen creature thaws when trying to move
+const THAW_CHANCE: u32 = 1;
+
+impl<M: MoveRegistry> Battle<'_, M> {
+    /// Gives the creature in `target` a status condition
+    ///
+    /// Fails if it has fainted, already has a status or is immune by type.
+    /// Sleep lasts between one and three turns, rolled by the battle RNG.
+    pub fn inflict_status(&mut self, target: (usize, usize), kind: StatusKind) -> bool {
+        self.log_intervention(Intervention::InflictStatus { target, kind });
+        self.apply_status(target, kind)
+    }
+
+    /// Inflicts a status as part of the battle itself, e.g. from a move
+    pub(super) fn apply_status(&mut self, (side, slot): (usize, usize), kind: StatusKind) -> bool {
+        let creature = self.active(side, slot);
+        if creature.is_fainted() || creature.status.is_some() || kind.is_immune(&creature.types) {
+            return false;
+        }
+
+        let status = match kind {
+            StatusKind::Poison => StatusCondition::Poison,
+            StatusKind::BadlyPoisoned => StatusCondition::BadlyPoisoned { turns: 1 },
+            StatusKind::Burn => StatusCondition::Burn,
+            StatusKind::Paralysis => StatusCondition::Paralysis,
+            StatusKind::Sleep => StatusCondition::Sleep {
+                turns_left: self.roll_sleep_turns(),
+            },
+            StatusKind::Freeze => StatusCondition::Freeze,
+        };
+        let creature = &mut self.parties[side].all_mut()[slot];
+        creature.status = Some(status);
+        self.events.push(BattleEvent::StatusInflicted {
+            creature: creature.id,
+            status: kind,
+        });
+        true
+    }
+
+    /// Rolls how many move attempts a new sleep lasts
+    pub(super) fn roll_sleep_turns(&mut self) -> u8 {
+        self.rng.range(1, 3) as u8
+    }
+
+    /// Checks whether the creature in `user` is able to act this turn
+    ///
+    /// Handles waking up, thawing and full paralysis, emitting the matching events.
+    pub(super) fn can_act(&mut self, (side, slot): (usize, usize)) -> bool {
+        let creature = self.active(side, slot);
+        let id = creature.id;
+        let Some(status) = creature.status else {
+            return true;
+        };
+
+        let (cured, immobilized) = match status {
+            StatusCondition::Sleep { turns_left: 0 } => (true, false),
+            StatusCondition::Sleep { turns_left } => {
+                self.parties[side].all_mut()[slot].status = Some(StatusCondition::Sleep {
+                    turns_left: turns_left - 1,
+                });
+                (false, true)
+            }
+            StatusCondition::Freeze => {
+                let thawed = self.rng.chance(THAW_CHANCE, 5);
+                (thawed, !thawed)
+            }
+            StatusCondition::Paralysis => (false, self.rng.chance(FULL_PARALYSIS_CHANCE, 4)),
+            _ => (false, false),
+        };
+
+        if cured {
+            self.parties[side].all_mut()[slot].status = None;
+            self.events.push(BattleEvent::StatusCured {
+                creature: id,
+                status: status.kind(),
+            });
+        }
+        if immobilized {
+            self.events.push(BattleEvent::Immobilized {
+                creature: id,
+                status: status.kind(),
+            });
+        }
+        !immobilized
+    }
+
+    /// Applies end-of-turn status damage to every active creature
+    pub(super) fn status_end_of_turn(&mut self) {
+        for side in 0..self.parties.len() {
+            for slot in 0..self.active_count(side) {
+                let creature = self.active(side, slot);
+                if creature.is_fainted() {
+                    continue;
+                }
+                let Some(status) = creature.status else {
+                    continue;
+                };
+                let max_hp = creature.individual_stats.max_hp.get();
+                let Some(damage) = status.end_of_turn_damage(max_hp) else {
+                    continue;
+                };
+
+                let id = creature.id;
+                self.apply_damage(id, (side, slot), damage);
+                if let StatusCondition::BadlyPoisoned { turns } = status {
+                    self.parties[side].all_mut()[slot].status =
+                        Some(StatusCondition::BadlyPoisoned {
+                            turns: turns.saturating_add(1),
+                        });
+                }
+            }
+        }
+    }
+}
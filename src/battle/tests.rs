@@ -1,29 +1,64 @@
+use crate::battle::error::ActionError;
+use crate::battle::format::{BattleFormat, BattleKind};
+use crate::battle::outcome::BattleResult;
+use crate::battle::rng::ScriptedRng;
+use crate::battle::side::SideCondition;
+use crate::battle::stages::{BattleStat, StatStages};
+use crate::battle::volatile::Volatile;
+use crate::battle::weather::Weather;
 #[cfg(test)]
 use crate::battle::*;
-use crate::creature::Creature;
+use crate::creature::CreatureId;
+use crate::creature_type::CreatureType;
+use crate::encounter::Encounter;
+use crate::event::BattleEvent;
+use crate::moves::{
+    EffectTarget, Fraction, HitCount, MoveCategory, MoveEffect, MoveId, MoveTarget, SecondaryEffect,
+};
 use crate::party::Party;
-use crate::species::SpeciesId;
-use crate::species_registry::SpeciesRegistry;
-use crate::tests::helpers::MockRegistry;
+use crate::stats::Stat;
+use crate::status::{StatusCondition, StatusKind};
+use crate::tests::helpers::{MockMoveRegistry, creature_at, full_party, start_selection};
 
-/// Helper to create a simple test creature
-fn make_test_creature() -> Creature {
-    let registry = MockRegistry::new();
-    let species = registry.get_species(SpeciesId(1)).unwrap();
-    Creature::new(species, 5).unwrap()
+/// Sets up a battle with two parties of 6 creatures each
+fn setup_battle(moves: &MockMoveRegistry) -> Battle<'_, MockMoveRegistry> {
+    Battle::new(full_party(), full_party(), moves, BattleFormat::Single, 1)
 }
 
-/// Sets up a battle with two parties of 6 creatures each
-fn setup_battle() -> Battle {
-    let creatures_p1 = std::array::from_fn(|_| make_test_creature());
-    let creatures_p2 = std::array::from_fn(|_| make_test_creature());
+/// Sets up a double battle with two parties of 6 creatures each
+fn setup_double_battle(moves: &MockMoveRegistry) -> Battle<'_, MockMoveRegistry> {
+    Battle::new(full_party(), full_party(), moves, BattleFormat::Double, 1)
+}
+
+/// Steps the battle through selection and queues an attack from side 0 onto side 1
+fn queue_attack(battle: &mut Battle<'_, MockMoveRegistry>, move_id: MoveId) {
+    let attacker_id = battle.parties[0].creatures[0].id;
+    let target_id = battle.parties[1].creatures[0].id;
+    battle.current_turn.add_action(BattleAction::Attack {
+        attacker_id,
+        target_id,
+        move_id,
+    });
+}
+
+/// Runs the battle until it has finished
+fn run_until_finished(battle: &mut Battle<'_, MockMoveRegistry>) {
+    while !battle.is_over() {
+        battle.process_turn();
+    }
+}
 
-    Battle::new(Party::new(creatures_p1), Party::new(creatures_p2))
+/// Runs the battle until the current turn's actions have been resolved
+fn run_until_resolved(battle: &mut Battle<'_, MockMoveRegistry>) {
+    while battle.state != BattleState::EndTurn {
+        battle.process_turn();
+    }
 }
 
 #[test]
 fn state_transitions_follow_expected_order() {
-    let mut battle = setup_battle();
+    let moves = MockMoveRegistry::new();
+    let mut battle = setup_battle(&moves);
 
     assert_eq!(battle.state, BattleState::StartTurn);
     assert_eq!(battle.current_turn.turn_number, 1);
@@ -45,10 +80,1919 @@ fn state_transitions_follow_expected_order() {
 
 #[test]
 fn battle_does_not_advance_when_finished() {
-    let mut battle = setup_battle();
+    let moves = MockMoveRegistry::new();
+    let mut battle = setup_battle(&moves);
     battle.state = BattleState::Finished;
 
     battle.advance_state();
     assert_eq!(battle.state, BattleState::Finished);
     assert_eq!(battle.current_turn.turn_number, 1);
 }
+
+#[test]
+fn attack_deals_damage_and_emits_event() {
+    let moves = MockMoveRegistry::new();
+    let mut battle = setup_battle(&moves);
+    queue_attack(&mut battle, MoveId(1));
+
+    run_until_resolved(&mut battle);
+
+    // Water Gun (80) vs Grass = 40 power, level 5, 10 atk vs 10 def -> 5 damage
+    let target = &battle.parties[1].creatures[0];
+    assert_eq!(target.current_hp, 15);
+
+    let events = battle.events.drain();
+    assert_eq!(events.len(), 1);
+    match &events[0] {
+        BattleEvent::Damage {
+            source,
+            target,
+            amount,
+        } => {
+            assert_eq!(*source, battle.parties[0].creatures[0].id);
+            assert_eq!(*target, battle.parties[1].creatures[0].id);
+            assert_eq!(*amount, 5);
+        }
+        _ => panic!("Expected Damage event"),
+    }
+}
+
+#[test]
+fn immune_target_takes_no_damage_event() {
+    let mut moves = MockMoveRegistry::new();
+    moves.get_mut(&MoveId(1)).unwrap().move_type = CreatureType::Electric;
+    let mut battle = setup_battle(&moves);
+    battle.parties[1].creatures[0].types = vec![CreatureType::Ground];
+    queue_attack(&mut battle, MoveId(1));
+
+    run_until_resolved(&mut battle);
+
+    assert_eq!(battle.active(1, 0).current_hp, 20);
+    assert_eq!(
+        battle.events.drain(),
+        vec![BattleEvent::NoEffect {
+            source: battle.active(0, 0).id,
+            target: battle.active(1, 0).id,
+        }]
+    );
+}
+
+#[test]
+fn attack_that_knocks_out_emits_fainted() {
+    let moves = MockMoveRegistry::new();
+    let mut battle = setup_battle(&moves);
+    battle.parties[1].creatures[0].current_hp = 3;
+    queue_attack(&mut battle, MoveId(1));
+
+    run_until_resolved(&mut battle);
+
+    let target_id = battle.parties[1].creatures[0].id;
+    assert!(battle.parties[1].creatures[0].is_fainted());
+
+    let events = battle.events.drain();
+    assert!(matches!(events[0], BattleEvent::Damage { amount: 3, .. }));
+    match &events[1] {
+        BattleEvent::Fainted { creature } => assert_eq!(*creature, target_id),
+        _ => panic!("Expected Fainted event"),
+    }
+}
+
+#[test]
+fn actions_are_only_resolved_in_resolve_phase() {
+    let moves = MockMoveRegistry::new();
+    let mut battle = setup_battle(&moves);
+    queue_attack(&mut battle, MoveId(1));
+
+    battle.process_turn(); // StartTurn -> SelectActions
+    battle.process_turn(); // SelectActions -> ResolveActions
+    assert!(battle.events.is_empty());
+
+    battle.process_turn(); // resolves, ResolveActions -> EndTurn
+    assert_eq!(battle.events.len(), 1);
+}
+
+#[test]
+fn unknown_move_is_ignored() {
+    let moves = MockMoveRegistry::new();
+    let mut battle = setup_battle(&moves);
+    queue_attack(&mut battle, MoveId(99));
+
+    run_until_resolved(&mut battle);
+
+    assert!(battle.events.is_empty());
+    assert_eq!(battle.parties[1].creatures[0].current_hp, 20);
+}
+
+fn attack(
+    battle: &Battle<'_, MockMoveRegistry>,
+    from: (usize, usize),
+    to: (usize, usize),
+    move_id: MoveId,
+) -> BattleAction {
+    BattleAction::Attack {
+        attacker_id: battle.parties[from.0].creatures[from.1].id,
+        target_id: battle.parties[to.0].creatures[to.1].id,
+        move_id,
+    }
+}
+
+fn attacker_order(battle: &Battle<'_, MockMoveRegistry>) -> Vec<CreatureId> {
+    battle
+        .current_turn
+        .actions
+        .iter()
+        .filter_map(|a| match a {
+            BattleAction::Attack { attacker_id, .. } => Some(*attacker_id),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn faster_creature_attacks_first() {
+    let moves = MockMoveRegistry::new();
+    let mut battle = setup_battle(&moves);
+    battle.parties[1].creatures[0].individual_stats.speed = Stat::new(99).unwrap();
+
+    let slow = attack(&battle, (0, 0), (1, 0), MoveId(1));
+    let fast = attack(&battle, (1, 0), (0, 0), MoveId(1));
+    battle.current_turn.add_action(slow);
+    battle.current_turn.add_action(fast);
+    run_until_resolved(&mut battle);
+
+    assert_eq!(
+        attacker_order(&battle),
+        vec![
+            battle.parties[1].creatures[0].id,
+            battle.parties[0].creatures[0].id
+        ]
+    );
+}
+
+#[test]
+fn move_priority_beats_speed() {
+    let mut moves = MockMoveRegistry::new();
+    moves.get_mut(&MoveId(2)).unwrap().priority = 1;
+    let mut battle = setup_battle(&moves);
+    battle.parties[1].creatures[0].individual_stats.speed = Stat::new(99).unwrap();
+
+    let quick = attack(&battle, (0, 0), (1, 0), MoveId(2));
+    let fast = attack(&battle, (1, 0), (0, 0), MoveId(1));
+    battle.current_turn.add_action(fast);
+    battle.current_turn.add_action(quick);
+    run_until_resolved(&mut battle);
+
+    assert_eq!(
+        attacker_order(&battle),
+        vec![
+            battle.parties[0].creatures[0].id,
+            battle.parties[1].creatures[0].id
+        ]
+    );
+}
+
+#[test]
+fn switches_resolve_before_attacks() {
+    let moves = MockMoveRegistry::new();
+    let mut battle = setup_battle(&moves);
+
+    let strike = attack(&battle, (0, 0), (1, 0), MoveId(1));
+    let switch = BattleAction::Switch {
+        out_id: battle.parties[1].creatures[0].id,
+        in_id: battle.parties[1].creatures[1].id,
+    };
+    battle.current_turn.add_action(BattleAction::Pass);
+    battle.current_turn.add_action(strike);
+    battle.current_turn.add_action(switch);
+    run_until_resolved(&mut battle);
+
+    let actions = &battle.current_turn.actions;
+    assert!(matches!(actions[0], BattleAction::Switch { .. }));
+    assert!(matches!(actions[1], BattleAction::Attack { .. }));
+    assert!(matches!(actions[2], BattleAction::Pass));
+}
+
+#[test]
+fn speed_ties_are_reproducible_for_a_seed() {
+    let moves = MockMoveRegistry::new();
+    let orders: Vec<_> = (0..2)
+        .map(|_| {
+            let mut battle = setup_battle(&moves);
+            // Same ids across runs so the orders can be compared
+            for side in 0..2 {
+                battle.parties[side].creatures[0].id =
+                    CreatureId::from_uuid(uuid::Uuid::from_u128(side as u128));
+            }
+            let a = attack(&battle, (0, 0), (1, 0), MoveId(1));
+            let b = attack(&battle, (1, 0), (0, 0), MoveId(1));
+            battle.current_turn.add_action(a);
+            battle.current_turn.add_action(b);
+            run_until_resolved(&mut battle);
+            attacker_order(&battle)
+        })
+        .collect();
+
+    assert_eq!(orders[0], orders[1]);
+}
+
+fn faint_all_but_first(battle: &mut Battle<'_, MockMoveRegistry>, side: usize) {
+    for c in battle.parties[side].creatures.iter_mut().skip(1) {
+        c.current_hp = 0;
+    }
+}
+
+#[test]
+fn battle_finishes_when_a_side_is_wiped_out() {
+    let moves = MockMoveRegistry::new();
+    let mut battle = setup_battle(&moves);
+    faint_all_but_first(&mut battle, 1);
+    battle.parties[1].creatures[0].current_hp = 1;
+    queue_attack(&mut battle, MoveId(1));
+
+    run_until_finished(&mut battle);
+
+    assert_eq!(battle.state, BattleState::Finished);
+    let outcome = battle.outcome().unwrap();
+    assert_eq!(outcome.result, BattleResult::Won { winner: 0 });
+    assert_eq!(outcome.winner(), Some(0));
+    assert_eq!(outcome.turns, 1);
+}
+
+#[test]
+fn battle_is_a_draw_when_both_sides_fall() {
+    let moves = MockMoveRegistry::new();
+    let mut battle = setup_battle(&moves);
+    for side in 0..2 {
+        for c in battle.parties[side].creatures.iter_mut() {
+            c.current_hp = 0;
+        }
+    }
+
+    run_until_finished(&mut battle);
+
+    assert!(battle.outcome().unwrap().is_draw());
+}
+
+#[test]
+fn battle_continues_while_both_sides_can_fight() {
+    let moves = MockMoveRegistry::new();
+    let mut battle = setup_battle(&moves);
+    queue_attack(&mut battle, MoveId(1));
+
+    run_until_resolved(&mut battle);
+
+    assert!(!battle.is_over());
+    assert!(battle.outcome().is_none());
+}
+
+#[test]
+fn forfeit_hands_win_to_other_side() {
+    let moves = MockMoveRegistry::new();
+    let mut battle = setup_battle(&moves);
+
+    battle.forfeit(1);
+
+    assert!(battle.is_over());
+    assert_eq!(
+        battle.outcome().unwrap().result,
+        BattleResult::Forfeit { winner: 0 }
+    );
+}
+
+#[test]
+fn fainted_active_forces_replacement_before_next_turn() {
+    let moves = MockMoveRegistry::new();
+    let mut battle = setup_battle(&moves);
+    battle.parties[1].creatures[0].current_hp = 1;
+    queue_attack(&mut battle, MoveId(1));
+
+    run_until_resolved(&mut battle);
+    battle.process_turn(); // EndTurn -> ReplaceFainted
+    assert_eq!(battle.state, BattleState::ReplaceFainted);
+    assert_eq!(battle.pending_replacements(), vec![1]);
+
+    // The battle refuses to move on until a choice is made
+    battle.process_turn();
+    assert_eq!(battle.state, BattleState::ReplaceFainted);
+    assert_eq!(battle.current_turn.turn_number, 1);
+
+    let fainted_id = battle.active(1, 0).id;
+    let bench_id = battle.parties[1].creatures[3].id;
+    battle.events.drain();
+    battle.replace_fainted(1, bench_id).unwrap();
+
+    assert_eq!(battle.active(1, 0).id, bench_id);
+    match battle.events.pop() {
+        Some(BattleEvent::SwitchedIn {
+            creature,
+            replacing,
+        }) => {
+            assert_eq!(creature, bench_id);
+            assert_eq!(replacing, fainted_id);
+        }
+        other => panic!("Expected SwitchedIn event, got {other:?}"),
+    }
+
+    battle.process_turn();
+    assert_eq!(battle.state, BattleState::StartTurn);
+    assert_eq!(battle.current_turn.turn_number, 2);
+}
+
+#[test]
+fn replacement_rejects_invalid_choices() {
+    let moves = MockMoveRegistry::new();
+    let mut battle = setup_battle(&moves);
+    let bench_id = battle.parties[1].creatures[1].id;
+
+    assert_eq!(
+        battle.replace_fainted(1, bench_id),
+        Err(ActionError::WrongState)
+    );
+
+    battle.parties[1].creatures[0].current_hp = 0;
+    battle.parties[1].creatures[2].current_hp = 0;
+    battle.state = BattleState::ReplaceFainted;
+
+    assert_eq!(
+        battle.replace_fainted(0, battle.parties[0].creatures[1].id),
+        Err(ActionError::NoReplacementNeeded { side: 0 })
+    );
+
+    let other_side = battle.parties[0].creatures[1].id;
+    assert_eq!(
+        battle.replace_fainted(1, other_side),
+        Err(ActionError::NotOnSide {
+            creature: other_side,
+            side: 1
+        })
+    );
+
+    let fainted = battle.parties[1].creatures[2].id;
+    assert_eq!(
+        battle.replace_fainted(1, fainted),
+        Err(ActionError::Fainted { creature: fainted })
+    );
+
+    assert!(battle.replace_fainted(1, bench_id).is_ok());
+}
+
+#[test]
+fn switch_action_brings_in_benched_creature() {
+    let moves = MockMoveRegistry::new();
+    let mut battle = setup_battle(&moves);
+    let out_id = battle.active(1, 0).id;
+    let in_id = battle.parties[1].creatures[2].id;
+
+    let strike = attack(&battle, (0, 0), (1, 0), MoveId(1));
+    battle.current_turn.add_action(strike);
+    battle
+        .current_turn
+        .add_action(BattleAction::Switch { out_id, in_id });
+    run_until_resolved(&mut battle);
+
+    // The switch resolves first, so the incoming creature takes the hit
+    assert_eq!(battle.active(1, 0).id, in_id);
+    assert_eq!(battle.active(1, 0).current_hp, 15);
+    assert_eq!(battle.parties[1].creatures[2].id, out_id);
+    assert_eq!(battle.parties[1].creatures[2].current_hp, 20);
+
+    let events = battle.events.drain();
+    assert!(matches!(events[0], BattleEvent::SwitchedIn { .. }));
+}
+
+#[test]
+fn battle_starts_with_first_creature_able_to_fight() {
+    let moves = MockMoveRegistry::new();
+    let mut party = full_party();
+    party.all_mut()[0].current_hp = 0;
+    party.all_mut()[1].current_hp = 0;
+    let lead_id = party.all()[2].id;
+
+    let battle = Battle::new(party, full_party(), &moves, BattleFormat::Single, 7);
+
+    assert_eq!(battle.active(0, 0).id, lead_id);
+}
+
+#[test]
+fn seeded_battle_records_its_seed() {
+    let moves = MockMoveRegistry::new();
+    let battle = setup_battle(&moves);
+    assert_eq!(battle.seed(), Some(1));
+
+    let scripted = Battle::with_rng(
+        full_party(),
+        full_party(),
+        &moves,
+        BattleFormat::Single,
+        Box::new(ScriptedRng::always(0)),
+    );
+    assert_eq!(scripted.seed(), None);
+}
+
+#[test]
+fn scripted_rng_decides_speed_ties() {
+    let moves = MockMoveRegistry::new();
+    for (rolls, first_side) in [(vec![0, 1], 1), (vec![1, 0], 0)] {
+        let mut battle = Battle::with_rng(
+            full_party(),
+            full_party(),
+            &moves,
+            BattleFormat::Single,
+            Box::new(ScriptedRng::new(rolls)),
+        );
+        let a = attack(&battle, (0, 0), (1, 0), MoveId(1));
+        let b = attack(&battle, (1, 0), (0, 0), MoveId(1));
+        battle.current_turn.add_action(a);
+        battle.current_turn.add_action(b);
+        run_until_resolved(&mut battle);
+
+        assert_eq!(attacker_order(&battle)[0], battle.active(first_side, 0).id);
+    }
+}
+
+#[test]
+fn double_battle_fields_two_creatures_per_side() {
+    let moves = MockMoveRegistry::new();
+    let battle = setup_double_battle(&moves);
+
+    for side in 0..2 {
+        assert_eq!(battle.actives(side).len(), 2);
+        assert_eq!(
+            battle.active(side, 1).id,
+            battle.parties[side].creatures[1].id
+        );
+    }
+    assert_eq!(setup_battle(&moves).actives(0).len(), 1);
+}
+
+#[test]
+fn double_battle_attacks_can_target_either_foe_or_ally() {
+    let moves = MockMoveRegistry::new();
+    let mut battle = setup_double_battle(&moves);
+
+    let at_second_foe = attack(&battle, (0, 0), (1, 1), MoveId(1));
+    let at_ally = attack(&battle, (0, 1), (0, 0), MoveId(1));
+    battle.current_turn.add_action(at_second_foe);
+    battle.current_turn.add_action(at_ally);
+    run_until_resolved(&mut battle);
+
+    assert_eq!(battle.active(1, 0).current_hp, 20);
+    assert_eq!(battle.active(1, 1).current_hp, 15);
+    // Water Gun on a Grass ally is still resisted: 5 damage
+    assert_eq!(battle.active(0, 0).current_hp, 15);
+}
+
+#[test]
+fn double_battle_attacks_on_benched_creatures_are_ignored() {
+    let moves = MockMoveRegistry::new();
+    let mut battle = setup_double_battle(&moves);
+
+    let at_bench = attack(&battle, (0, 0), (1, 3), MoveId(1));
+    battle.current_turn.add_action(at_bench);
+    run_until_resolved(&mut battle);
+
+    assert!(battle.events.is_empty());
+}
+
+#[test]
+fn double_battle_switches_per_slot() {
+    let moves = MockMoveRegistry::new();
+    let mut battle = setup_double_battle(&moves);
+    let out_id = battle.active(1, 1).id;
+    let in_id = battle.parties[1].creatures[4].id;
+    let kept_id = battle.active(1, 0).id;
+
+    let strike = attack(&battle, (0, 0), (1, 1), MoveId(1));
+    battle.current_turn.add_action(strike);
+    battle
+        .current_turn
+        .add_action(BattleAction::Switch { out_id, in_id });
+    run_until_resolved(&mut battle);
+
+    assert_eq!(battle.active(1, 0).id, kept_id);
+    assert_eq!(battle.active(1, 1).id, in_id);
+    // The attack aimed at slot 1 hits the creature that switched in
+    assert_eq!(battle.active(1, 1).current_hp, 15);
+}
+
+#[test]
+fn double_battle_replacement_fills_fainted_slot() {
+    let moves = MockMoveRegistry::new();
+    let mut battle = setup_double_battle(&moves);
+    battle.parties[1].creatures[1].current_hp = 1;
+    let strike = attack(&battle, (0, 0), (1, 1), MoveId(1));
+    battle.current_turn.add_action(strike);
+
+    run_until_resolved(&mut battle);
+    battle.process_turn();
+    assert_eq!(battle.state, BattleState::ReplaceFainted);
+
+    let bench_id = battle.parties[1].creatures[5].id;
+    battle.replace_fainted(1, bench_id).unwrap();
+
+    assert_eq!(battle.active(1, 1).id, bench_id);
+    assert!(battle.pending_replacements().is_empty());
+}
+
+#[test]
+fn double_battle_fields_a_lone_creature_in_one_slot() {
+    let mut moves = MockMoveRegistry::new();
+    moves.get_mut(&MoveId(1)).unwrap().target = MoveTarget::AllOpponents;
+    let mut battle = Battle::new(
+        full_party(),
+        Party::new([creature_at(5)]).unwrap(),
+        &moves,
+        BattleFormat::Double,
+        1,
+    );
+    assert_eq!(battle.actives(0).len(), 2);
+    assert_eq!(battle.actives(1).len(), 1);
+    assert!(battle.view(1).bench().is_empty());
+    assert!(battle.pending_replacements().is_empty());
+
+    let surf = attack(&battle, (0, 0), (1, 0), MoveId(1));
+    battle.current_turn.add_action(surf);
+    run_turn(&mut battle);
+    // A lone target takes the full hit, without the spread modifier
+    assert_eq!(battle.active(1, 0).current_hp, 15);
+
+    battle.parties[1].creatures[0].current_hp = 1;
+    let surf = attack(&battle, (0, 1), (1, 0), MoveId(1));
+    battle.current_turn.add_action(surf);
+    run_until_finished(&mut battle);
+    assert_eq!(battle.outcome().unwrap().winner(), Some(0));
+}
+
+#[test]
+fn spread_move_hits_every_opponent_for_reduced_damage() {
+    let mut moves = MockMoveRegistry::new();
+    moves.get_mut(&MoveId(1)).unwrap().target = MoveTarget::AllOpponents;
+    let mut battle = setup_double_battle(&moves);
+
+    let surf = attack(&battle, (0, 0), (1, 0), MoveId(1));
+    battle.current_turn.add_action(surf);
+    run_until_resolved(&mut battle);
+
+    // 40 power * 0.75 spread modifier -> 4 damage to each foe
+    assert_eq!(battle.active(1, 0).current_hp, 16);
+    assert_eq!(battle.active(1, 1).current_hp, 16);
+    assert_eq!(battle.active(0, 1).current_hp, 20);
+}
+
+#[test]
+fn single_target_move_is_redirected_when_target_has_fainted() {
+    let moves = MockMoveRegistry::new();
+    let mut battle = setup_double_battle(&moves);
+    battle.parties[1].creatures[0].current_hp = 1;
+
+    let first = attack(&battle, (0, 0), (1, 0), MoveId(1));
+    let second = attack(&battle, (0, 1), (1, 0), MoveId(1));
+    battle.current_turn.add_action(first);
+    battle.current_turn.add_action(second);
+    run_until_resolved(&mut battle);
+
+    assert!(battle.active(1, 0).is_fainted());
+    assert_eq!(battle.active(1, 1).current_hp, 15);
+}
+
+#[test]
+fn targets_resolve_from_move_target_kind() {
+    let moves = MockMoveRegistry::new();
+    let doubles = setup_double_battle(&moves);
+    let user = (0, 0);
+
+    assert_eq!(
+        doubles.resolve_targets(user, None, MoveTarget::User),
+        vec![(0, 0)]
+    );
+    assert_eq!(
+        doubles.resolve_targets(user, None, MoveTarget::Ally),
+        vec![(0, 1)]
+    );
+    assert_eq!(
+        doubles.resolve_targets(user, None, MoveTarget::AllOthers),
+        vec![(1, 0), (1, 1), (0, 1)]
+    );
+    assert_eq!(
+        doubles.resolve_targets(user, Some((1, 1)), MoveTarget::Single),
+        vec![(1, 1)]
+    );
+    assert!(
+        doubles
+            .resolve_targets(user, Some(user), MoveTarget::Single)
+            .is_empty()
+    );
+    assert!(
+        doubles
+            .resolve_targets(user, None, MoveTarget::Field)
+            .is_empty()
+    );
+
+    let singles = setup_battle(&moves);
+    assert!(
+        singles
+            .resolve_targets(user, None, MoveTarget::Ally)
+            .is_empty()
+    );
+}
+
+fn scripted_battle(moves: &MockMoveRegistry, rolls: Vec<u32>) -> Battle<'_, MockMoveRegistry> {
+    Battle::with_rng(
+        full_party(),
+        full_party(),
+        moves,
+        BattleFormat::Single,
+        Box::new(ScriptedRng::new(rolls)),
+    )
+}
+
+/// Plays one full turn, ending in the state after `EndTurn`
+fn run_turn(battle: &mut Battle<'_, MockMoveRegistry>) {
+    run_until_resolved(battle);
+    battle.process_turn();
+}
+
+#[test]
+fn burn_halves_attack_damage() {
+    let moves = MockMoveRegistry::new();
+    let mut battle = setup_battle(&moves);
+    battle.parties[0].creatures[0].status = Some(StatusCondition::Burn);
+    queue_attack(&mut battle, MoveId(2));
+
+    run_until_resolved(&mut battle);
+
+    // Attack 10 halved to 5: 4 * 40 * 5 / 10 / 50 + 2 = 3
+    assert_eq!(battle.active(1, 0).current_hp, 17);
+}
+
+#[test]
+fn poison_deals_damage_at_end_of_turn() {
+    let moves = MockMoveRegistry::new();
+    let mut battle = setup_battle(&moves);
+    assert!(battle.inflict_status((1, 0), StatusKind::Poison));
+    let id = battle.active(1, 0).id;
+
+    run_turn(&mut battle);
+
+    assert_eq!(battle.active(1, 0).current_hp, 18);
+    let events = battle.events.drain();
+    assert_eq!(
+        events,
+        vec![
+            BattleEvent::StatusInflicted {
+                creature: id,
+                status: StatusKind::Poison
+            },
+            BattleEvent::Damage {
+                source: id,
+                target: id,
+                amount: 2
+            },
+        ]
+    );
+}
+
+#[test]
+fn bad_poison_grows_every_turn() {
+    let moves = MockMoveRegistry::new();
+    let mut battle = setup_battle(&moves);
+    battle.inflict_status((1, 0), StatusKind::BadlyPoisoned);
+
+    run_turn(&mut battle);
+    assert_eq!(battle.active(1, 0).current_hp, 19);
+    run_turn(&mut battle);
+    assert_eq!(battle.active(1, 0).current_hp, 17);
+    assert_eq!(
+        battle.active(1, 0).status,
+        Some(StatusCondition::BadlyPoisoned { turns: 3 })
+    );
+}
+
+#[test]
+fn full_paralysis_skips_the_attack() {
+    let moves = MockMoveRegistry::new();
+    let mut battle = scripted_battle(&moves, vec![0]);
+    battle.inflict_status((0, 0), StatusKind::Paralysis);
+    battle.events.drain();
+    queue_attack(&mut battle, MoveId(1));
+
+    run_until_resolved(&mut battle);
+
+    assert_eq!(battle.active(1, 0).current_hp, 20);
+    assert!(matches!(
+        battle.events.pop(),
+        Some(BattleEvent::Immobilized {
+            status: StatusKind::Paralysis,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn paralysis_halves_speed_for_ordering() {
+    let moves = MockMoveRegistry::new();
+    let mut battle = setup_battle(&moves);
+    battle.parties[0].creatures[0].individual_stats.speed = Stat::new(15).unwrap();
+    battle.parties[0].creatures[0].status = Some(StatusCondition::Paralysis);
+
+    let a = attack(&battle, (0, 0), (1, 0), MoveId(1));
+    let b = attack(&battle, (1, 0), (0, 0), MoveId(1));
+    battle.current_turn.add_action(a);
+    battle.current_turn.add_action(b);
+    run_until_resolved(&mut battle);
+
+    assert_eq!(attacker_order(&battle)[0], battle.active(1, 0).id);
+}
+
+#[test]
+fn sleeping_creature_skips_turns_then_wakes() {
+    let moves = MockMoveRegistry::new();
+    // A roll of 0 puts the creature to sleep for a single turn, and the
+    // final roll keeps the attack after waking from being a critical hit
+    let mut battle = scripted_battle(&moves, vec![0, 0, 0, 23]);
+    battle.inflict_status((0, 0), StatusKind::Sleep);
+    assert_eq!(
+        battle.active(0, 0).status,
+        Some(StatusCondition::Sleep { turns_left: 1 })
+    );
+
+    queue_attack(&mut battle, MoveId(1));
+    run_turn(&mut battle);
+    assert_eq!(battle.active(1, 0).current_hp, 20);
+
+    queue_attack(&mut battle, MoveId(1));
+    run_turn(&mut battle);
+    assert_eq!(battle.active(0, 0).status, None);
+    assert_eq!(battle.active(1, 0).current_hp, 15);
+}
+
+#[test]
+fn frozen_creature_can_thaw() {
+    let moves = MockMoveRegistry::new();
+    let mut battle = scripted_battle(&moves, vec![4]);
+    battle.parties[0].creatures[0].status = Some(StatusCondition::Freeze);
+
+    queue_attack(&mut battle, MoveId(1));
+    run_turn(&mut battle);
+    assert_eq!(battle.active(1, 0).current_hp, 20);
+    assert_eq!(battle.active(0, 0).status, Some(StatusCondition::Freeze));
+
+    let mut battle = scripted_battle(&moves, vec![0, 0, 23]);
+    battle.parties[0].creatures[0].status = Some(StatusCondition::Freeze);
+    queue_attack(&mut battle, MoveId(1));
+    run_turn(&mut battle);
+    assert_eq!(battle.active(0, 0).status, None);
+    assert_eq!(battle.active(1, 0).current_hp, 15);
+}
+
+#[test]
+fn status_cannot_stack_or_hit_immune_types() {
+    let moves = MockMoveRegistry::new();
+    let mut battle = setup_battle(&moves);
+
+    assert!(battle.inflict_status((1, 0), StatusKind::Burn));
+    assert!(!battle.inflict_status((1, 0), StatusKind::Poison));
+
+    battle.parties[1].creatures[0].types = vec![CreatureType::Fire];
+    assert!(!battle.inflict_status((1, 0), StatusKind::Burn));
+}
+
+#[test]
+fn flinch_blocks_the_move_and_wears_off() {
+    let moves = MockMoveRegistry::new();
+    let mut battle = setup_battle(&moves);
+    assert!(battle.add_volatile((0, 0), Volatile::Flinch, (1, 0)));
+    queue_attack(&mut battle, MoveId(1));
+
+    run_turn(&mut battle);
+
+    assert_eq!(battle.active(1, 0).current_hp, 20);
+    let events = battle.events.drain();
+    assert!(events.contains(&BattleEvent::ActionBlocked {
+        creature: battle.active(0, 0).id,
+        condition: Volatile::Flinch,
+    }));
+    assert!(!battle.volatiles(0, 0).has(Volatile::Flinch));
+}
+
+#[test]
+fn confused_creature_can_hit_itself() {
+    let moves = MockMoveRegistry::new();
+    let mut battle = scripted_battle(&moves, vec![0]);
+    battle.add_volatile((0, 0), Volatile::Confusion, (1, 0));
+    queue_attack(&mut battle, MoveId(1));
+
+    run_until_resolved(&mut battle);
+
+    // 40 power typeless hit with 10 atk vs 10 def -> 5 damage to itself
+    assert_eq!(battle.active(0, 0).current_hp, 15);
+    assert_eq!(battle.active(1, 0).current_hp, 20);
+}
+
+#[test]
+fn protect_shields_from_attacks_for_one_turn() {
+    let moves = MockMoveRegistry::new();
+    let mut battle = setup_battle(&moves);
+    battle.add_volatile((1, 0), Volatile::Protect, (1, 0));
+    queue_attack(&mut battle, MoveId(1));
+
+    run_turn(&mut battle);
+    assert_eq!(battle.active(1, 0).current_hp, 20);
+    assert!(battle.events.drain().contains(&BattleEvent::Protected {
+        source: battle.active(0, 0).id,
+        target: battle.active(1, 0).id,
+    }));
+
+    queue_attack(&mut battle, MoveId(1));
+    run_turn(&mut battle);
+    assert_eq!(battle.active(1, 0).current_hp, 15);
+}
+
+#[test]
+fn leech_seed_drains_into_the_seeder() {
+    let moves = MockMoveRegistry::new();
+    let mut battle = setup_battle(&moves);
+    battle.parties[1].creatures[0].types = vec![CreatureType::Water];
+    battle.parties[0].creatures[0].current_hp = 10;
+    assert!(battle.add_volatile((1, 0), Volatile::LeechSeed, (0, 0)));
+
+    run_turn(&mut battle);
+
+    assert_eq!(battle.active(1, 0).current_hp, 18);
+    assert_eq!(battle.active(0, 0).current_hp, 12);
+    // Seeding lasts until the creature leaves the field
+    assert!(battle.volatiles(1, 0).has(Volatile::LeechSeed));
+}
+
+#[test]
+fn grass_types_cannot_be_seeded() {
+    let moves = MockMoveRegistry::new();
+    let mut battle = setup_battle(&moves);
+    assert!(!battle.add_volatile((1, 0), Volatile::LeechSeed, (0, 0)));
+}
+
+#[test]
+fn taunt_blocks_status_moves_until_it_expires() {
+    let mut moves = MockMoveRegistry::new();
+    let growl = moves.get_mut(&MoveId(2)).unwrap();
+    growl.category = MoveCategory::Status;
+    growl.power = 0;
+    let mut battle = setup_battle(&moves);
+    battle.add_volatile((0, 0), Volatile::Taunt, (1, 0));
+
+    for _ in 0..3 {
+        let growl = attack(&battle, (0, 0), (1, 0), MoveId(2));
+        battle.current_turn.add_action(growl);
+        run_turn(&mut battle);
+        assert!(battle.events.drain().contains(&BattleEvent::ActionBlocked {
+            creature: battle.active(0, 0).id,
+            condition: Volatile::Taunt,
+        }));
+    }
+    assert!(!battle.volatiles(0, 0).has(Volatile::Taunt));
+}
+
+#[test]
+fn volatiles_are_cleared_on_switch_out() {
+    let moves = MockMoveRegistry::new();
+    let mut battle = setup_battle(&moves);
+    battle.add_volatile((1, 0), Volatile::Confusion, (0, 0));
+    let out_id = battle.active(1, 0).id;
+    let in_id = battle.parties[1].creatures[1].id;
+    battle
+        .current_turn
+        .add_action(BattleAction::Switch { out_id, in_id });
+
+    run_until_resolved(&mut battle);
+
+    assert!(!battle.volatiles(1, 0).has(Volatile::Confusion));
+}
+
+#[test]
+fn attack_and_defense_stages_change_damage() {
+    let moves = MockMoveRegistry::new();
+    let mut battle = setup_battle(&moves);
+    assert_eq!(battle.change_stat((0, 0), BattleStat::Attack, 2), 2);
+    queue_attack(&mut battle, MoveId(2));
+    run_turn(&mut battle);
+    // Attack doubled to 20: 4 * 40 * 20 / 10 / 50 + 2 = 8
+    assert_eq!(battle.active(1, 0).current_hp, 12);
+
+    let mut battle = setup_battle(&moves);
+    battle.change_stat((1, 0), BattleStat::Defense, 6);
+    queue_attack(&mut battle, MoveId(2));
+    run_turn(&mut battle);
+    // Defense quadrupled to 40: 4 * 40 * 10 / 40 / 50 + 2 = 2
+    assert_eq!(battle.active(1, 0).current_hp, 18);
+}
+
+#[test]
+fn speed_stage_changes_turn_order() {
+    let moves = MockMoveRegistry::new();
+    let mut battle = setup_battle(&moves);
+    battle.parties[1].creatures[0].individual_stats.speed = Stat::new(14).unwrap();
+    battle.change_stat((0, 0), BattleStat::Speed, 1);
+
+    let a = attack(&battle, (1, 0), (0, 0), MoveId(1));
+    let b = attack(&battle, (0, 0), (1, 0), MoveId(1));
+    battle.current_turn.add_action(a);
+    battle.current_turn.add_action(b);
+    run_until_resolved(&mut battle);
+
+    assert_eq!(attacker_order(&battle)[0], battle.active(0, 0).id);
+}
+
+#[test]
+fn stat_changes_report_limits() {
+    let moves = MockMoveRegistry::new();
+    let mut battle = setup_battle(&moves);
+    let id = battle.active(0, 0).id;
+
+    assert_eq!(battle.change_stat((0, 0), BattleStat::Speed, 5), 5);
+    assert_eq!(battle.change_stat((0, 0), BattleStat::Speed, 2), 1);
+    assert_eq!(battle.change_stat((0, 0), BattleStat::Speed, 1), 0);
+
+    let events = battle.events.drain();
+    assert_eq!(
+        events[2],
+        BattleEvent::StatLimitReached {
+            creature: id,
+            stat: BattleStat::Speed,
+            raising: true
+        }
+    );
+    assert_eq!(battle.stages(0, 0).get(BattleStat::Speed), 6);
+}
+
+#[test]
+fn stat_stages_reset_on_switch_out() {
+    let moves = MockMoveRegistry::new();
+    let mut battle = setup_battle(&moves);
+    battle.change_stat((1, 0), BattleStat::Evasion, -3);
+    let out_id = battle.active(1, 0).id;
+    let in_id = battle.parties[1].creatures[1].id;
+    battle
+        .current_turn
+        .add_action(BattleAction::Switch { out_id, in_id });
+
+    run_until_resolved(&mut battle);
+
+    assert_eq!(*battle.stages(1, 0), StatStages::default());
+}
+
+#[test]
+fn rain_boosts_water_moves() {
+    let moves = MockMoveRegistry::new();
+    let mut battle = setup_battle(&moves);
+    assert!(battle.set_weather(Weather::Rain, Some(5)));
+    queue_attack(&mut battle, MoveId(1));
+
+    run_until_resolved(&mut battle);
+
+    // 40 power * 1.5 in rain: 4 * 60 / 50 + 2 = 6
+    assert_eq!(battle.active(1, 0).current_hp, 14);
+}
+
+#[test]
+fn sandstorm_chips_exposed_creatures_at_end_of_turn() {
+    let moves = MockMoveRegistry::new();
+    let mut battle = setup_battle(&moves);
+    battle.parties[1].creatures[0].types = vec![CreatureType::Rock];
+    battle.set_weather(Weather::Sandstorm, None);
+
+    run_turn(&mut battle);
+
+    assert_eq!(battle.active(0, 0).current_hp, 19);
+    assert_eq!(battle.active(1, 0).current_hp, 20);
+}
+
+#[test]
+fn weather_runs_out_after_its_duration() {
+    let moves = MockMoveRegistry::new();
+    let mut battle = setup_battle(&moves);
+    battle.set_weather(Weather::Sun, Some(2));
+    assert!(!battle.set_weather(Weather::Sun, Some(2)));
+    battle.events.drain();
+
+    run_turn(&mut battle);
+    assert_eq!(
+        battle.events.drain(),
+        vec![BattleEvent::WeatherContinues {
+            weather: Weather::Sun
+        }]
+    );
+    assert_eq!(battle.weather(), Some(Weather::Sun));
+
+    run_turn(&mut battle);
+    assert_eq!(
+        battle.events.drain(),
+        vec![BattleEvent::WeatherEnded {
+            weather: Weather::Sun
+        }]
+    );
+    assert_eq!(battle.weather(), None);
+}
+
+/// Queues a switch on side 1 from the active creature to party slot `bench`
+fn queue_switch(battle: &mut Battle<'_, MockMoveRegistry>, bench: usize) {
+    let out_id = battle.active(1, 0).id;
+    let in_id = battle.parties[1].creatures[bench].id;
+    battle
+        .current_turn
+        .add_action(BattleAction::Switch { out_id, in_id });
+}
+
+#[test]
+fn reflect_halves_damage_until_it_expires() {
+    let moves = MockMoveRegistry::new();
+    let mut battle = setup_battle(&moves);
+    assert!(battle.add_side_condition(1, SideCondition::Reflect));
+    // Fire Wheel (20) is super effective on Grass for the same 40 power
+    queue_attack(&mut battle, MoveId(2));
+
+    run_turn(&mut battle);
+    assert_eq!(battle.active(1, 0).current_hp, 18);
+
+    for _ in 0..4 {
+        run_turn(&mut battle);
+    }
+    assert!(!battle.side_conditions(1).has(SideCondition::Reflect));
+    assert!(
+        battle
+            .events
+            .drain()
+            .contains(&BattleEvent::SideConditionEnded {
+                side: 1,
+                condition: SideCondition::Reflect
+            })
+    );
+}
+
+#[test]
+fn tailwind_doubles_side_speed() {
+    let moves = MockMoveRegistry::new();
+    let mut battle = setup_battle(&moves);
+    battle.parties[1].creatures[0].individual_stats.speed = Stat::new(19).unwrap();
+    battle.add_side_condition(0, SideCondition::Tailwind);
+
+    let a = attack(&battle, (1, 0), (0, 0), MoveId(1));
+    let b = attack(&battle, (0, 0), (1, 0), MoveId(1));
+    battle.current_turn.add_action(a);
+    battle.current_turn.add_action(b);
+    run_until_resolved(&mut battle);
+
+    assert_eq!(attacker_order(&battle)[0], battle.active(0, 0).id);
+}
+
+#[test]
+fn spikes_damage_creatures_switching_in() {
+    let moves = MockMoveRegistry::new();
+    let mut battle = setup_battle(&moves);
+    battle.add_side_condition(1, SideCondition::Spikes);
+    battle.add_side_condition(1, SideCondition::Spikes);
+    queue_switch(&mut battle, 1);
+
+    run_until_resolved(&mut battle);
+
+    // Two layers deal 1/6 of 20 max HP
+    assert_eq!(battle.active(1, 0).current_hp, 17);
+    assert!(
+        battle
+            .events
+            .drain()
+            .contains(&BattleEvent::HazardTriggered {
+                creature: battle.active(1, 0).id,
+                hazard: SideCondition::Spikes
+            })
+    );
+}
+
+#[test]
+fn stealth_rock_scales_with_rock_effectiveness() {
+    let moves = MockMoveRegistry::new();
+    let mut battle = setup_battle(&moves);
+    battle.parties[1].creatures[1].types = vec![CreatureType::Fire];
+    battle.parties[1].creatures[2].types = vec![CreatureType::Ground];
+    battle.add_side_condition(1, SideCondition::StealthRock);
+
+    queue_switch(&mut battle, 1);
+    run_turn(&mut battle);
+    // Fire is weak to Rock: 20 * 2 / 8
+    assert_eq!(battle.active(1, 0).current_hp, 15);
+
+    queue_switch(&mut battle, 2);
+    run_turn(&mut battle);
+    // Ground resists Rock: 20 * 0.5 / 8
+    assert_eq!(battle.active(1, 0).current_hp, 19);
+}
+
+#[test]
+fn toxic_spikes_poison_on_entry() {
+    let moves = MockMoveRegistry::new();
+    let mut battle = setup_battle(&moves);
+    battle.add_side_condition(1, SideCondition::ToxicSpikes);
+    queue_switch(&mut battle, 1);
+
+    run_until_resolved(&mut battle);
+
+    assert_eq!(battle.active(1, 0).status, Some(StatusCondition::Poison));
+}
+
+#[test]
+fn hazards_can_be_cleared() {
+    let moves = MockMoveRegistry::new();
+    let mut battle = setup_battle(&moves);
+    battle.add_side_condition(1, SideCondition::Spikes);
+    battle.add_side_condition(1, SideCondition::StealthRock);
+    battle.add_side_condition(1, SideCondition::Reflect);
+    battle.events.drain();
+
+    battle.clear_hazards(1);
+
+    let conditions: Vec<_> = battle.side_conditions(1).iter().collect();
+    assert_eq!(conditions, vec![SideCondition::Reflect]);
+    assert_eq!(battle.events.len(), 2);
+}
+
+#[test]
+fn critical_hit_multiplies_damage_and_emits_event() {
+    let moves = MockMoveRegistry::new();
+    let mut battle = scripted_battle(&moves, vec![0]);
+    let source = battle.active(0, 0).id;
+    let target = battle.active(1, 0).id;
+    queue_attack(&mut battle, MoveId(1));
+
+    run_until_resolved(&mut battle);
+
+    // 5 damage * 1.5 = 7
+    assert_eq!(battle.active(1, 0).current_hp, 13);
+    assert_eq!(
+        battle.events.drain(),
+        vec![
+            BattleEvent::CriticalHit { source, target },
+            BattleEvent::Damage {
+                source,
+                target,
+                amount: 7
+            },
+        ]
+    );
+
+    let mut battle = scripted_battle(&moves, vec![0]);
+    battle.set_crit_multiplier(2.0);
+    queue_attack(&mut battle, MoveId(1));
+    run_until_resolved(&mut battle);
+    assert_eq!(battle.active(1, 0).current_hp, 10);
+}
+
+#[test]
+fn critical_hit_ignores_unfavourable_stages_and_screens() {
+    let moves = MockMoveRegistry::new();
+    let mut battle = scripted_battle(&moves, vec![0]);
+    battle.change_stat((0, 0), BattleStat::SpecialAttack, -2);
+    battle.change_stat((1, 0), BattleStat::SpecialDefense, 2);
+    battle.add_side_condition(1, SideCondition::LightScreen);
+    queue_attack(&mut battle, MoveId(1));
+
+    run_until_resolved(&mut battle);
+
+    assert_eq!(battle.active(1, 0).current_hp, 13);
+}
+
+#[test]
+fn crit_stages_raise_the_chance() {
+    let mut moves = MockMoveRegistry::new();
+    let mut battle = scripted_battle(&moves, vec![5]);
+    queue_attack(&mut battle, MoveId(1));
+    run_until_resolved(&mut battle);
+    assert_eq!(battle.active(1, 0).current_hp, 15);
+
+    // A high-crit move plus two focused stages always lands a critical hit
+    moves.get_mut(&MoveId(1)).unwrap().crit_stage = 1;
+    let mut battle = scripted_battle(&moves, vec![5]);
+    assert_eq!(battle.raise_crit_stage((0, 0), 2), 2);
+    assert_eq!(battle.raise_crit_stage((0, 0), 2), 1);
+    queue_attack(&mut battle, MoveId(1));
+    run_until_resolved(&mut battle);
+    assert_eq!(battle.active(1, 0).current_hp, 13);
+}
+
+#[test]
+fn inaccurate_move_can_miss() {
+    let mut moves = MockMoveRegistry::new();
+    moves.get_mut(&MoveId(1)).unwrap().accuracy = Some(50);
+
+    let mut battle = scripted_battle(&moves, vec![60]);
+    let source = battle.active(0, 0).id;
+    let target = battle.active(1, 0).id;
+    queue_attack(&mut battle, MoveId(1));
+    run_until_resolved(&mut battle);
+    assert_eq!(battle.active(1, 0).current_hp, 20);
+    assert_eq!(
+        battle.events.drain(),
+        vec![BattleEvent::Miss { source, target }]
+    );
+
+    let mut battle = scripted_battle(&moves, vec![49]);
+    queue_attack(&mut battle, MoveId(1));
+    run_until_resolved(&mut battle);
+    assert_eq!(battle.active(1, 0).current_hp, 15);
+}
+
+#[test]
+fn evasion_and_accuracy_stages_offset_each_other() {
+    let moves = MockMoveRegistry::new();
+    let mut battle = scripted_battle(&moves, vec![80]);
+    battle.change_stat((1, 0), BattleStat::Evasion, 1);
+    queue_attack(&mut battle, MoveId(1));
+    run_until_resolved(&mut battle);
+    // Accuracy 100 * 0.75 = 75, so a roll of 80 misses
+    assert_eq!(battle.active(1, 0).current_hp, 20);
+
+    let mut battle = scripted_battle(&moves, vec![80]);
+    battle.change_stat((1, 0), BattleStat::Evasion, 1);
+    battle.change_stat((0, 0), BattleStat::Accuracy, 1);
+    queue_attack(&mut battle, MoveId(1));
+    run_until_resolved(&mut battle);
+    assert_eq!(battle.active(1, 0).current_hp, 15);
+}
+
+#[test]
+fn never_miss_move_ignores_evasion() {
+    let mut moves = MockMoveRegistry::new();
+    moves.get_mut(&MoveId(1)).unwrap().accuracy = None;
+    let mut battle = scripted_battle(&moves, vec![99]);
+    battle.change_stat((1, 0), BattleStat::Evasion, 6);
+    queue_attack(&mut battle, MoveId(1));
+
+    run_until_resolved(&mut battle);
+
+    assert_eq!(battle.active(1, 0).current_hp, 15);
+}
+
+#[test]
+fn special_moves_use_special_stats() {
+    let moves = MockMoveRegistry::new();
+    let mut battle = setup_battle(&moves);
+    battle.parties[0].creatures[0]
+        .individual_stats
+        .special_attack = Stat::new(20).unwrap();
+    battle.parties[0].creatures[0].status = Some(StatusCondition::Burn);
+    queue_attack(&mut battle, MoveId(1));
+
+    run_until_resolved(&mut battle);
+
+    // Special attack 20, unaffected by the burn: 4 * 40 * 20 / 10 / 50 + 2 = 8
+    assert_eq!(battle.active(1, 0).current_hp, 12);
+}
+
+#[test]
+fn screens_only_weaken_their_category() {
+    let moves = MockMoveRegistry::new();
+    let mut battle = setup_battle(&moves);
+    battle.add_side_condition(1, SideCondition::Reflect);
+    queue_attack(&mut battle, MoveId(1));
+    run_until_resolved(&mut battle);
+    assert_eq!(battle.active(1, 0).current_hp, 15);
+
+    let mut battle = setup_battle(&moves);
+    battle.add_side_condition(1, SideCondition::LightScreen);
+    queue_attack(&mut battle, MoveId(1));
+    run_until_resolved(&mut battle);
+    assert_eq!(battle.active(1, 0).current_hp, 18);
+}
+
+#[test]
+fn status_moves_deal_no_damage() {
+    let mut moves = MockMoveRegistry::new();
+    moves.get_mut(&MoveId(1)).unwrap().category = MoveCategory::Status;
+    let mut battle = setup_battle(&moves);
+    queue_attack(&mut battle, MoveId(1));
+
+    run_until_resolved(&mut battle);
+
+    assert_eq!(battle.active(1, 0).current_hp, 20);
+    assert!(battle.events.is_empty());
+}
+
+fn damage_events(events: &[BattleEvent]) -> usize {
+    events
+        .iter()
+        .filter(|e| matches!(e, BattleEvent::Damage { .. }))
+        .count()
+}
+
+#[test]
+fn multi_hit_move_strikes_a_rolled_number_of_times() {
+    let mut moves = MockMoveRegistry::new();
+    moves.get_mut(&MoveId(1)).unwrap().hits = HitCount::Between { min: 2, max: 5 };
+    // Tie-breaker, then a hit roll of 1 for three hits, then no critical hits
+    let mut battle = scripted_battle(&moves, vec![1, 1, 23, 23, 23]);
+    queue_attack(&mut battle, MoveId(1));
+
+    run_until_resolved(&mut battle);
+
+    assert_eq!(damage_events(&battle.events.drain()), 3);
+    assert_eq!(battle.active(1, 0).current_hp, 5);
+}
+
+#[test]
+fn multi_hit_move_stops_when_target_faints() {
+    let mut moves = MockMoveRegistry::new();
+    moves.get_mut(&MoveId(1)).unwrap().hits = HitCount::Fixed(5);
+    let mut battle = scripted_battle(&moves, vec![23]);
+    queue_attack(&mut battle, MoveId(1));
+
+    run_until_resolved(&mut battle);
+
+    let events = battle.events.drain();
+    assert_eq!(damage_events(&events), 4);
+    assert!(battle.active(1, 0).is_fainted());
+}
+
+#[test]
+fn recoil_hurts_the_user() {
+    let mut moves = MockMoveRegistry::new();
+    moves.get_mut(&MoveId(1)).unwrap().recoil = Some(Fraction::new(1, 4));
+    let mut battle = setup_battle(&moves);
+    let user = battle.active(0, 0).id;
+    queue_attack(&mut battle, MoveId(1));
+
+    run_until_resolved(&mut battle);
+
+    assert_eq!(battle.active(0, 0).current_hp, 19);
+    assert_eq!(
+        battle.events.drain().last(),
+        Some(&BattleEvent::Damage {
+            source: user,
+            target: user,
+            amount: 1
+        })
+    );
+}
+
+#[test]
+fn drain_heals_the_user() {
+    let mut moves = MockMoveRegistry::new();
+    moves.get_mut(&MoveId(1)).unwrap().drain = Some(Fraction::new(1, 2));
+    let mut battle = setup_battle(&moves);
+    battle.parties[0].creatures[0].current_hp = 10;
+    let user = battle.active(0, 0).id;
+    queue_attack(&mut battle, MoveId(1));
+
+    run_until_resolved(&mut battle);
+
+    assert_eq!(battle.active(0, 0).current_hp, 12);
+    assert_eq!(
+        battle.events.drain().last(),
+        Some(&BattleEvent::Heal {
+            source: user,
+            target: user,
+            amount: 2
+        })
+    );
+}
+
+#[test]
+fn secondary_status_depends_on_its_chance() {
+    let mut moves = MockMoveRegistry::new();
+    moves.get_mut(&MoveId(2)).unwrap().effects = vec![SecondaryEffect::with_chance(
+        MoveEffect::Status {
+            target: EffectTarget::Target,
+            status: StatusKind::Burn,
+        },
+        10,
+    )];
+
+    // Tie-breaker, critical hit and effect rolls all come up 5
+    let mut battle = scripted_battle(&moves, vec![5]);
+    queue_attack(&mut battle, MoveId(2));
+    run_until_resolved(&mut battle);
+    assert_eq!(battle.active(1, 0).current_hp, 15);
+    assert_eq!(battle.active(1, 0).status, Some(StatusCondition::Burn));
+
+    let mut battle = scripted_battle(&moves, vec![50]);
+    queue_attack(&mut battle, MoveId(2));
+    run_until_resolved(&mut battle);
+    assert_eq!(battle.active(1, 0).status, None);
+}
+
+#[test]
+fn stat_effects_land_on_user_or_target() {
+    let mut moves = MockMoveRegistry::new();
+    let growl = moves.get_mut(&MoveId(2)).unwrap();
+    growl.category = MoveCategory::Status;
+    growl.effects = vec![SecondaryEffect::always(MoveEffect::StatChange {
+        target: EffectTarget::Target,
+        stat: BattleStat::Attack,
+        change: -1,
+    })];
+    let dance = moves.get_mut(&MoveId(3)).unwrap();
+    dance.category = MoveCategory::Status;
+    dance.target = MoveTarget::User;
+    dance.effects = vec![SecondaryEffect::always(MoveEffect::StatChange {
+        target: EffectTarget::User,
+        stat: BattleStat::Attack,
+        change: 2,
+    })];
+    let mut battle = setup_battle(&moves);
+
+    let a = attack(&battle, (0, 0), (1, 0), MoveId(2));
+    let b = attack(&battle, (1, 0), (1, 0), MoveId(3));
+    battle.current_turn.add_action(a);
+    battle.current_turn.add_action(b);
+    run_until_resolved(&mut battle);
+
+    assert_eq!(battle.active(1, 0).current_hp, 20);
+    assert_eq!(battle.stages(1, 0).get(BattleStat::Attack), 1);
+    assert_eq!(battle.stages(0, 0).get(BattleStat::Attack), 0);
+}
+
+#[test]
+fn field_moves_set_weather_and_hazards() {
+    let mut moves = MockMoveRegistry::new();
+    let rain_dance = moves.get_mut(&MoveId(2)).unwrap();
+    rain_dance.category = MoveCategory::Status;
+    rain_dance.target = MoveTarget::Field;
+    rain_dance.effects = vec![
+        SecondaryEffect::always(MoveEffect::Weather(Weather::Rain)),
+        SecondaryEffect::always(MoveEffect::SideCondition {
+            target: EffectTarget::Target,
+            condition: SideCondition::Spikes,
+        }),
+    ];
+    let mut battle = setup_battle(&moves);
+    queue_attack(&mut battle, MoveId(2));
+
+    run_until_resolved(&mut battle);
+
+    assert_eq!(battle.weather(), Some(Weather::Rain));
+    assert!(battle.side_conditions(1).has(SideCondition::Spikes));
+    assert!(!battle.side_conditions(0).has(SideCondition::Spikes));
+}
+
+#[test]
+fn moves_can_clear_hazards_and_break_screens() {
+    let mut moves = MockMoveRegistry::new();
+    moves.get_mut(&MoveId(2)).unwrap().effects = vec![
+        SecondaryEffect::always(MoveEffect::ClearHazards {
+            target: EffectTarget::User,
+        }),
+        SecondaryEffect::always(MoveEffect::RemoveSideCondition {
+            target: EffectTarget::Target,
+            condition: SideCondition::Reflect,
+        }),
+    ];
+    let mut battle = setup_battle(&moves);
+    battle.add_side_condition(0, SideCondition::Spikes);
+    battle.add_side_condition(0, SideCondition::StealthRock);
+    battle.add_side_condition(0, SideCondition::Tailwind);
+    battle.add_side_condition(1, SideCondition::Reflect);
+    queue_attack(&mut battle, MoveId(2));
+
+    run_until_resolved(&mut battle);
+
+    let own = battle.side_conditions(0);
+    assert!(!own.has(SideCondition::Spikes));
+    assert!(!own.has(SideCondition::StealthRock));
+    assert!(own.has(SideCondition::Tailwind));
+    assert!(!battle.side_conditions(1).has(SideCondition::Reflect));
+}
+
+#[test]
+fn flinch_effect_stops_a_slower_target() {
+    let mut moves = MockMoveRegistry::new();
+    moves.get_mut(&MoveId(1)).unwrap().effects =
+        vec![SecondaryEffect::always(MoveEffect::Volatile {
+            target: EffectTarget::Target,
+            condition: Volatile::Flinch,
+        })];
+    let mut battle = setup_battle(&moves);
+    battle.parties[1].creatures[0].individual_stats.speed = Stat::new(5).unwrap();
+
+    let a = attack(&battle, (0, 0), (1, 0), MoveId(1));
+    let b = attack(&battle, (1, 0), (0, 0), MoveId(1));
+    battle.current_turn.add_action(a);
+    battle.current_turn.add_action(b);
+    run_until_resolved(&mut battle);
+
+    assert_eq!(battle.active(0, 0).current_hp, 20);
+    assert!(battle.events.drain().contains(&BattleEvent::ActionBlocked {
+        creature: battle.active(1, 0).id,
+        condition: Volatile::Flinch,
+    }));
+}
+
+#[test]
+fn heal_effect_restores_share_of_max_hp() {
+    let mut moves = MockMoveRegistry::new();
+    let recover = moves.get_mut(&MoveId(2)).unwrap();
+    recover.category = MoveCategory::Status;
+    recover.target = MoveTarget::User;
+    recover.effects = vec![SecondaryEffect::always(MoveEffect::Heal(Fraction::new(
+        1, 2,
+    )))];
+    let mut battle = setup_battle(&moves);
+    battle.parties[0].creatures[0].current_hp = 5;
+    let action = attack(&battle, (0, 0), (0, 0), MoveId(2));
+    battle.current_turn.add_action(action);
+
+    run_until_resolved(&mut battle);
+
+    assert_eq!(battle.active(0, 0).current_hp, 15);
+}
+
+#[test]
+fn effects_do_not_apply_when_the_move_is_blocked() {
+    let mut moves = MockMoveRegistry::new();
+    moves.get_mut(&MoveId(1)).unwrap().effects = vec![
+        SecondaryEffect::always(MoveEffect::Status {
+            target: EffectTarget::Target,
+            status: StatusKind::Poison,
+        }),
+        SecondaryEffect::always(MoveEffect::StatChange {
+            target: EffectTarget::User,
+            stat: BattleStat::Speed,
+            change: 1,
+        }),
+    ];
+    let mut battle = setup_battle(&moves);
+    battle.add_volatile((1, 0), Volatile::Protect, (1, 0));
+    queue_attack(&mut battle, MoveId(1));
+
+    run_until_resolved(&mut battle);
+
+    assert_eq!(battle.active(1, 0).status, None);
+    assert_eq!(battle.stages(0, 0).get(BattleStat::Speed), 0);
+}
+
+#[test]
+fn using_a_move_spends_pp_on_the_creature() {
+    let moves = MockMoveRegistry::new();
+    let mut battle = setup_battle(&moves);
+    battle.parties[0].creatures[0].try_learn_move(MoveId(1), 20);
+    start_selection(&mut battle);
+
+    let action = attack(&battle, (0, 0), (1, 0), MoveId(1));
+    battle.submit_action(action).unwrap();
+    run_until_resolved(&mut battle);
+
+    let known = battle.active(0, 0).moves[0].as_ref().unwrap();
+    assert_eq!(known.pp.current, 19);
+}
+
+#[test]
+fn submitted_attacks_need_a_known_move_with_pp() {
+    let moves = MockMoveRegistry::new();
+    let mut battle = setup_battle(&moves);
+    battle.parties[0].creatures[0].try_learn_move(MoveId(1), 20);
+    battle.parties[0].creatures[0].moves[0]
+        .as_mut()
+        .unwrap()
+        .pp
+        .current = 0;
+    let id = battle.active(0, 0).id;
+
+    let action = attack(&battle, (0, 0), (1, 0), MoveId(1));
+    assert_eq!(
+        battle.submit_action(action.clone()),
+        Err(ActionError::WrongState)
+    );
+    start_selection(&mut battle);
+
+    assert_eq!(
+        battle.submit_action(action),
+        Err(ActionError::NoPp {
+            creature: id,
+            move_id: MoveId(1)
+        })
+    );
+    let unknown = attack(&battle, (0, 0), (1, 0), MoveId(2));
+    assert_eq!(
+        battle.submit_action(unknown),
+        Err(ActionError::MoveNotKnown {
+            creature: id,
+            move_id: MoveId(2)
+        })
+    );
+    let benched = battle.parties[0].creatures[1].id;
+    let from_bench = BattleAction::Attack {
+        attacker_id: benched,
+        target_id: battle.active(1, 0).id,
+        move_id: MoveId(1),
+    };
+    assert_eq!(
+        battle.submit_action(from_bench),
+        Err(ActionError::NotActive { creature: benched })
+    );
+    assert!(battle.current_turn.actions.is_empty());
+}
+
+#[test]
+fn each_creature_chooses_one_action_a_turn() {
+    let moves = MockMoveRegistry::new();
+    let mut battle = setup_battle(&moves);
+    battle.parties[0].creatures[0].try_learn_move(MoveId(1), 20);
+    let id = battle.active(0, 0).id;
+    start_selection(&mut battle);
+
+    let action = attack(&battle, (0, 0), (1, 0), MoveId(1));
+    assert_eq!(battle.submit_action(action.clone()), Ok(()));
+    assert_eq!(
+        battle.submit_action(action),
+        Err(ActionError::AlreadyChosen { creature: id })
+    );
+    assert_eq!(
+        battle.submit_action(BattleAction::Flee { creature_id: id }),
+        Err(ActionError::AlreadyChosen { creature: id })
+    );
+    assert_eq!(battle.submit_action(BattleAction::Pass), Ok(()));
+    assert_eq!(battle.current_turn.actions.len(), 2);
+}
+
+#[test]
+fn struggle_is_only_allowed_without_usable_moves() {
+    let moves = MockMoveRegistry::new();
+    let mut battle = scripted_battle(&moves, vec![23]);
+    battle.parties[0].creatures[0].try_learn_move(MoveId(1), 1);
+    let user = battle.active(0, 0).id;
+    let target = battle.active(1, 0).id;
+    start_selection(&mut battle);
+    let struggle = BattleAction::Struggle {
+        attacker_id: user,
+        target_id: target,
+    };
+    assert_eq!(
+        battle.submit_action(struggle.clone()),
+        Err(ActionError::HasUsableMoves { creature: user })
+    );
+
+    battle.parties[0].creatures[0].moves[0]
+        .as_mut()
+        .unwrap()
+        .pp
+        .current = 0;
+    assert!(battle.usable_moves(0, 0).is_empty());
+    battle.submit_action(struggle).unwrap();
+    run_until_resolved(&mut battle);
+
+    // Normal 50 power: 4 * 50 * 10 / 10 / 50 + 2 = 6, with a quarter as recoil
+    assert_eq!(battle.active(1, 0).current_hp, 14);
+    assert_eq!(battle.active(0, 0).current_hp, 19);
+    assert_eq!(
+        battle.events.drain().last(),
+        Some(&BattleEvent::Damage {
+            source: user,
+            target: user,
+            amount: 1
+        })
+    );
+}
+
+fn flee(battle: &Battle<'_, MockMoveRegistry>, side: usize) -> BattleAction {
+    BattleAction::Flee {
+        creature_id: battle.active(side, 0).id,
+    }
+}
+
+#[test]
+fn fleeing_is_rejected_against_trainers_and_for_the_wild_side() {
+    let moves = MockMoveRegistry::new();
+    let mut battle = setup_battle(&moves);
+    start_selection(&mut battle);
+    assert_eq!(
+        battle.submit_action(flee(&battle, 0)),
+        Err(ActionError::TrainerBattle)
+    );
+
+    battle.set_kind(BattleKind::Wild { catch_rate: 45 });
+    assert_eq!(
+        battle.submit_action(flee(&battle, 1)),
+        Err(ActionError::NotPlayerSide)
+    );
+    assert!(battle.submit_action(flee(&battle, 0)).is_ok());
+}
+
+#[test]
+fn trapped_creature_cannot_flee_or_switch() {
+    let moves = MockMoveRegistry::new();
+    let mut battle = setup_battle(&moves);
+    battle.set_kind(BattleKind::Wild { catch_rate: 45 });
+    battle.add_volatile((0, 0), Volatile::Trapped, (1, 0));
+    start_selection(&mut battle);
+    let id = battle.active(0, 0).id;
+
+    assert_eq!(
+        battle.submit_action(flee(&battle, 0)),
+        Err(ActionError::Trapped { creature: id })
+    );
+    let switch = BattleAction::Switch {
+        out_id: id,
+        in_id: battle.parties[0].creatures[1].id,
+    };
+    assert_eq!(
+        battle.submit_action(switch),
+        Err(ActionError::Trapped { creature: id })
+    );
+}
+
+#[test]
+fn faster_creature_flees_before_the_foe_acts() {
+    let moves = MockMoveRegistry::new();
+    let mut battle = setup_battle(&moves);
+    battle.set_kind(BattleKind::Wild { catch_rate: 45 });
+    start_selection(&mut battle);
+    let runner = battle.active(0, 0).id;
+
+    battle.submit_action(flee(&battle, 0)).unwrap();
+    let foe_attack = attack(&battle, (1, 0), (0, 0), MoveId(1));
+    battle.current_turn.add_action(foe_attack);
+    while !battle.is_over() {
+        battle.process_turn();
+    }
+
+    assert_eq!(
+        battle.outcome().unwrap().result,
+        BattleResult::Fled { side: 0 }
+    );
+    assert_eq!(battle.outcome().unwrap().winner(), None);
+    assert_eq!(battle.active(0, 0).current_hp, 20);
+    assert_eq!(
+        battle.events.drain(),
+        vec![BattleEvent::Fled { creature: runner }]
+    );
+}
+
+#[test]
+fn escape_odds_improve_with_each_attempt() {
+    let moves = MockMoveRegistry::new();
+    let mut battle = scripted_battle(&moves, vec![100]);
+    battle.set_kind(BattleKind::Wild { catch_rate: 45 });
+    battle.parties[0].creatures[0].individual_stats.speed = Stat::new(5).unwrap();
+    let runner = battle.active(0, 0).id;
+
+    // 94 / 256 on the first attempt misses a roll of 100
+    start_selection(&mut battle);
+    battle.submit_action(flee(&battle, 0)).unwrap();
+    run_turn(&mut battle);
+    assert!(!battle.is_over());
+    assert_eq!(
+        battle.events.drain(),
+        vec![BattleEvent::FleeFailed { creature: runner }]
+    );
+
+    // 124 / 256 on the second attempt gets away
+    start_selection(&mut battle);
+    battle.submit_action(flee(&battle, 0)).unwrap();
+    battle.process_turn(); // SelectActions -> ResolveActions
+    battle.process_turn();
+    assert!(battle.is_over());
+}
+
+#[test]
+fn balls_can_only_be_thrown_in_wild_battles() {
+    let moves = MockMoveRegistry::new();
+    let mut battle = setup_battle(&moves);
+    start_selection(&mut battle);
+    let throw = BattleAction::Capture {
+        thrower_id: battle.active(0, 0).id,
+        ball_modifier: 1.0,
+    };
+
+    assert_eq!(
+        battle.submit_action(throw.clone()),
+        Err(ActionError::TrainerBattle)
+    );
+    battle.set_kind(BattleKind::Wild { catch_rate: 45 });
+    assert!(battle.submit_action(throw).is_ok());
+}
+
+fn throw_ball(battle: &Battle<'_, MockMoveRegistry>, ball_modifier: f32) -> BattleAction {
+    BattleAction::Capture {
+        thrower_id: battle.active(0, 0).id,
+        ball_modifier,
+    }
+}
+
+#[test]
+fn ball_shakes_three_times_before_a_catch() {
+    let moves = MockMoveRegistry::new();
+    let mut battle = scripted_battle(&moves, vec![0]);
+    battle.set_kind(BattleKind::Wild { catch_rate: 45 });
+    let wild = battle.active(1, 0).id;
+
+    start_selection(&mut battle);
+    battle.submit_action(throw_ball(&battle, 1.0)).unwrap();
+    battle.process_turn(); // SelectActions -> ResolveActions
+    battle.process_turn();
+
+    assert_eq!(
+        battle.outcome().unwrap().result,
+        BattleResult::Captured { creature: wild }
+    );
+    assert_eq!(
+        battle.events.drain(),
+        vec![
+            BattleEvent::BallShook {
+                creature: wild,
+                shakes: 1
+            },
+            BattleEvent::BallShook {
+                creature: wild,
+                shakes: 2
+            },
+            BattleEvent::BallShook {
+                creature: wild,
+                shakes: 3
+            },
+            BattleEvent::Captured { creature: wild },
+        ]
+    );
+}
+
+#[test]
+fn creature_breaks_free_on_a_failed_shake() {
+    let moves = MockMoveRegistry::new();
+    // The first roll orders the turn; a full-HP creature with catch rate 45
+    // then needs shake rolls under 32275
+    let mut battle = scripted_battle(&moves, vec![0, 0, 40000]);
+    battle.set_kind(BattleKind::Wild { catch_rate: 45 });
+    let wild = battle.active(1, 0).id;
+
+    start_selection(&mut battle);
+    battle.submit_action(throw_ball(&battle, 1.0)).unwrap();
+    run_turn(&mut battle);
+
+    assert!(!battle.is_over());
+    assert_eq!(
+        battle.events.drain(),
+        vec![
+            BattleEvent::BallShook {
+                creature: wild,
+                shakes: 1
+            },
+            BattleEvent::BrokeFree { creature: wild },
+        ]
+    );
+}
+
+#[test]
+fn weakened_sleeping_creature_is_caught_with_a_better_ball() {
+    let moves = MockMoveRegistry::new();
+    // 40000 breaks out of a plain ball at full health, but not here
+    let mut battle = scripted_battle(&moves, vec![40000]);
+    battle.set_kind(BattleKind::Wild { catch_rate: 45 });
+    battle.parties[1].creatures[0].current_hp = 1;
+    battle.parties[1].creatures[0].status = Some(StatusCondition::Sleep { turns_left: 3 });
+
+    start_selection(&mut battle);
+    battle.submit_action(throw_ball(&battle, 1.5)).unwrap();
+    battle.process_turn(); // SelectActions -> ResolveActions
+    battle.process_turn();
+
+    assert!(matches!(
+        battle.outcome().unwrap().result,
+        BattleResult::Captured { .. }
+    ));
+}
+
+#[test]
+fn simulated_copy_plays_out_without_touching_the_battle() {
+    let moves = MockMoveRegistry::new();
+    let mut battle = setup_battle(&moves);
+    battle.parties[0].creatures[0].try_learn_move(MoveId(1), 20);
+    start_selection(&mut battle);
+
+    let mut sim = battle.simulate(5);
+    sim.submit_action(attack(&sim, (0, 0), (1, 0), MoveId(1)))
+        .unwrap();
+    run_turn(&mut sim);
+
+    assert!(sim.active(1, 0).current_hp < 20);
+    assert!(sim.events.is_empty());
+    assert!(sim.record().is_none());
+    assert_eq!(battle.active(1, 0).current_hp, 20);
+    assert_eq!(battle.state, BattleState::SelectActions);
+    assert!(battle.record().is_some());
+}
+
+#[test]
+fn hazards_knocking_out_the_last_replacement_end_the_battle() {
+    let moves = MockMoveRegistry::new();
+    let mut battle = setup_battle(&moves);
+    battle.add_side_condition(1, SideCondition::StealthRock);
+    for creature in battle.parties[1].all_mut() {
+        creature.current_hp = 0;
+    }
+    battle.parties[1].creatures[1].current_hp = 1;
+    battle.state = BattleState::ReplaceFainted;
+
+    let last = battle.parties[1].creatures[1].id;
+    battle.replace_fainted(1, last).unwrap();
+
+    assert!(battle.is_over());
+    assert_eq!(
+        battle.outcome().unwrap().result,
+        BattleResult::Won { winner: 0 }
+    );
+}
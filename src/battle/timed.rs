@@ -0,0 +1,30 @@
+/// An entry in a list of conditions that may run out after some turns
+pub(super) trait Timed {
+    type Condition: Copy;
+
+    fn condition(&self) -> Self::Condition;
+
+    /// End-of-turn ticks left; `None` never runs out on its own
+    fn turns_left(&mut self) -> &mut Option<u8>;
+}
+
+/// Counts down every timed entry, removing and returning the conditions of
+/// those that ran out
+pub(super) fn tick<E: Timed>(entries: &mut Vec<E>) -> Vec<E::Condition> {
+    let mut expired = Vec::new();
+    entries.retain_mut(|e| {
+        let condition = e.condition();
+        match e.turns_left() {
+            Some(turns) if *turns <= 1 => {
+                expired.push(condition);
+                false
+            }
+            Some(turns) => {
+                *turns -= 1;
+                true
+            }
+            None => true,
+        }
+    });
+    expired
+}
@@ -0,0 +1,451 @@
+use super::format::{BattleFormat, BattleKind};
+use super::side::SideState;
+use super::stages::StatStages;
+use super::volatile::Volatile;
+use super::weather::Weather;
+use super::{Battle, BattleState};
+use crate::creature::{Creature, CreatureId};
+use crate::creature_type::CreatureType;
+use crate::experience::Level;
+use crate::moves::{Move, MoveId, MoveRegistry};
+use crate::species::SpeciesId;
+use crate::status::{StatusCondition, StatusKind};
+
+/// What one side has let its opponent see of its team
+#[derive(Debug, Clone, Default)]
+pub(super) struct Revealed {
+    /// Creatures that have been on the field, with the moves seen from each
+    creatures: Vec<(CreatureId, Vec<MoveId>)>,
+}
+
+impl Revealed {
+    fn moves_seen(&self, id: CreatureId) -> Option<&[MoveId]> {
+        self.creatures
+            .iter()
+            .find(|(seen, _)| *seen == id)
+            .map(|(_, moves)| moves.as_slice())
+    }
+
+    fn creature(&mut self, id: CreatureId) -> &mut Vec<MoveId> {
+        let idx = match self.creatures.iter().position(|(seen, _)| *seen == id) {
+            Some(idx) => idx,
+            None => {
+                self.creatures.push((id, Vec::new()));
+                self.creatures.len() - 1
+            }
+        };
+        &mut self.creatures[idx].1
+    }
+}
+
+/// What a side knows about one of its opponent's creatures
+#[derive(Debug, Clone, PartialEq)]
+pub struct OpponentCreature {
+    pub id: CreatureId,
+    pub species_id: SpeciesId,
+    pub name: String,
+    pub types: Vec<CreatureType>,
+    pub level: Level,
+    /// Remaining HP as a percentage of the maximum, never 0 while it stands
+    pub hp_percent: u8,
+    pub status: Option<StatusKind>,
+    /// Moves it has been seen using, in the order they were first used
+    pub moves_seen: Vec<MoveId>,
+}
+
+impl OpponentCreature {
+    pub fn is_fainted(&self) -> bool {
+        self.hp_percent == 0
+    }
+}
+
+/// Share of its maximum HP a creature has left, rounded up so that only a
+/// fainted creature shows 0
+pub fn hp_percent(current_hp: u16, max_hp: u16) -> u8 {
+    if current_hp == 0 {
+        return 0;
+    }
+    (current_hp as u32 * 100)
+        .div_ceil(max_hp.max(1) as u32)
+        .min(100) as u8
+}
+
+/// Read-only look at a battle from one side's point of view
+///
+/// The side sees its own team in full, but of the opponent only the
+/// creatures that have been on the field, their HP as a percentage and the
+/// moves they have used. This is what a [`BattleAi`](super::ai::BattleAi)
+/// decides from, and what a front end should show a player.
+pub struct BattleView<'v, M: MoveRegistry> {
+    battle: &'v Battle<'v, M>,
+    side: usize,
+}
+
+impl<'v, M: MoveRegistry> BattleView<'v, M> {
+    /// The side this view belongs to
+    pub fn side(&self) -> usize {
+        self.side
+    }
+
+    /// The side being fought against
+    pub fn opponent(&self) -> usize {
+        1 - self.side
+    }
+
+    pub fn format(&self) -> BattleFormat {
+        self.battle.format()
+    }
+
+    pub fn kind(&self) -> BattleKind {
+        self.battle.kind()
+    }
+
+    pub fn state(&self) -> BattleState {
+        self.battle.state
+    }
+
+    pub fn turn_number(&self) -> u32 {
+        self.battle.current_turn.turn_number
+    }
+
+    /// Every creature of this view's side, active ones first
+    pub fn party(&self) -> &'v [Creature] {
+        self.battle.parties[self.side].all()
+    }
+
+    /// This side's creatures in their active slots, fainted or not
+    pub fn actives(&self) -> &'v [Creature] {
+        self.battle.actives(self.side)
+    }
+
+    /// This side's creatures waiting on the bench
+    pub fn bench(&self) -> &'v [Creature] {
+        &self.party()[self.actives().len()..]
+    }
+
+    /// Moves this side's creature in active `slot` still has PP for
+    pub fn usable_moves(&self, slot: usize) -> Vec<MoveId> {
+        self.battle.usable_moves(self.side, slot)
+    }
+
+    /// Whether this side's creature in active `slot` may switch out
+    pub fn can_switch(&self, slot: usize) -> bool {
+        !self
+            .battle
+            .volatiles(self.side, slot)
+            .has(Volatile::Trapped)
+    }
+
+    /// The opponent's creatures in their active slots, fainted or not
+    pub fn opponent_actives(&self) -> Vec<OpponentCreature> {
+        self.battle
+            .actives(self.opponent())
+            .iter()
+            .map(|c| self.describe(c))
+            .collect()
+    }
+
+    /// Every opposing creature that has been on the field so far
+    pub fn revealed_opponents(&self) -> Vec<OpponentCreature> {
+        let revealed = &self.battle.revealed[self.opponent()];
+        self.battle.parties[self.opponent()]
+            .all()
+            .iter()
+            .filter(|c| revealed.moves_seen(c.id).is_some())
+            .map(|c| self.describe(c))
+            .collect()
+    }
+
+    /// How many creatures the opponent brought, seen or not
+    pub fn opponent_team_size(&self) -> usize {
+        self.battle.parties[self.opponent()].all().len()
+    }
+
+    /// Looks up the data of a move
+    pub fn move_data(&self, id: &MoveId) -> Option<&'v Move> {
+        self.battle.moves.get(id)
+    }
+
+    pub fn stages(&self, side: usize, slot: usize) -> &'v StatStages {
+        self.battle.stages(side, slot)
+    }
+
+    pub fn side_conditions(&self, side: usize) -> &'v SideState {
+        self.battle.side_conditions(side)
+    }
+
+    pub fn weather(&self) -> Option<Weather> {
+        self.battle.weather()
+    }
+
+    /// A copy of the battle to try choices out on; see [`Battle::simulate`]
+    ///
+    /// The copy leaves out opposing creatures this side has not seen, and
+    /// the ones it keeps know only the moves seen so far. Their stats are
+    /// estimated from their level, with HP rebuilt from the percentage shown.
+    /// Actions the opponent has already picked this turn are dropped, PP is
+    /// refilled and the lengths of sleep and confusion are rolled afresh.
+    pub fn simulate(&self, seed: u64) -> Battle<'v, M> {
+        let opponent = self.opponent();
+        let mut sim = self.battle.simulate(seed);
+        sim.current_turn.actions.retain(|action| {
+            action
+                .actor()
+                .and_then(|id| self.battle.locate(id))
+                .is_none_or(|(side, _)| side != opponent)
+        });
+
+        let revealed = &self.battle.revealed[opponent];
+        sim.parties[opponent]
+            .creatures
+            .retain(|c| revealed.moves_seen(c.id).is_some());
+        for idx in 0..sim.parties[opponent].creatures.len() {
+            if let Some(StatusCondition::Sleep { .. }) = sim.parties[opponent].creatures[idx].status
+            {
+                let turns_left = sim.roll_sleep_turns();
+                sim.parties[opponent].creatures[idx].status =
+                    Some(StatusCondition::Sleep { turns_left });
+            }
+
+            let creature = &mut sim.parties[opponent].creatures[idx];
+            let percent = hp_percent(creature.current_hp, creature.individual_stats.max_hp.get());
+            creature.individual_stats = Creature::estimated_stats(creature.level);
+            let max_hp = creature.individual_stats.max_hp.get();
+            creature.current_hp = (max_hp as u32 * percent as u32).div_ceil(100) as u16;
+
+            let seen = revealed.moves_seen(creature.id).unwrap_or_default();
+            for slot in &mut creature.moves {
+                match slot {
+                    Some(m) if seen.contains(&m.move_id) => m.pp.current = m.pp.max,
+                    _ => *slot = None,
+                }
+            }
+        }
+        for slot in 0..sim.actives(opponent).len() {
+            sim.reroll_hidden_volatiles((opponent, slot));
+        }
+        sim
+    }
+
+    fn describe(&self, creature: &Creature) -> OpponentCreature {
+        OpponentCreature {
+            id: creature.id,
+            species_id: creature.species_id,
+            name: creature.name.clone(),
+            types: creature.types.clone(),
+            level: creature.level,
+            hp_percent: hp_percent(creature.current_hp, creature.individual_stats.max_hp.get()),
+            status: creature.status.map(|s| s.kind()),
+            moves_seen: self.battle.revealed[self.opponent()]
+                .moves_seen(creature.id)
+                .unwrap_or_default()
+                .to_vec(),
+        }
+    }
+}
+
+impl<'a, M: MoveRegistry> Battle<'a, M> {
+    /// A read-only view of the battle for `side`
+    pub fn view(&self, side: usize) -> BattleView<'_, M> {
+        BattleView { battle: self, side }
+    }
+
+    /// Shows the creature in an active slot to the other side
+    pub(super) fn reveal(&mut self, (side, slot): (usize, usize)) {
+        let id = self.active(side, slot).id;
+        self.revealed[side].creature(id);
+    }
+
+    /// Shows the other side that the creature in `user` knows `move_id`
+    pub(super) fn reveal_move(&mut self, (side, slot): (usize, usize), move_id: &MoveId) {
+        let creature = self.active(side, slot);
+        let id = creature.id;
+        if !creature
+            .moves
+            .iter()
+            .flatten()
+            .any(|m| m.move_id == *move_id)
+        {
+            return;
+        }
+        let seen = self.revealed[side].creature(id);
+        if !seen.contains(move_id) {
+            seen.push(move_id.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::battle::BattleAction;
+    use crate::battle::ai::GreedyAi;
+    use crate::encounter::Encounter;
+    use crate::tests::helpers::{self, MockMoveRegistry, creature, start_selection};
+
+    /// Two creatures a side, with the leads knowing Water Gun and Fire Wheel
+    fn battle(moves: &MockMoveRegistry) -> Battle<'_, MockMoveRegistry> {
+        let party = || {
+            vec![
+                creature(&[CreatureType::Grass], &[1, 2]),
+                creature(&[CreatureType::Grass], &[1]),
+            ]
+        };
+        helpers::battle(moves, BattleFormat::Single, party(), party())
+    }
+
+    /// Submits `actions` and plays the turn out until the next selection
+    fn play_turn(battle: &mut Battle<'_, MockMoveRegistry>, actions: Vec<BattleAction>) {
+        for action in actions {
+            battle.submit_action(action).unwrap();
+        }
+        battle.process_turn();
+        start_selection(battle);
+    }
+
+    #[test]
+    fn hp_percent_only_shows_zero_once_fainted() {
+        assert_eq!(hp_percent(0, 300), 0);
+        assert_eq!(hp_percent(1, 300), 1);
+        assert_eq!(hp_percent(150, 300), 50);
+        assert_eq!(hp_percent(299, 300), 100);
+        assert_eq!(hp_percent(300, 300), 100);
+    }
+
+    #[test]
+    fn own_team_is_shown_in_full() {
+        let moves = MockMoveRegistry::new();
+        let battle = battle(&moves);
+        let view = battle.view(0);
+
+        assert_eq!(view.party().len(), 2);
+        assert_eq!(view.bench().len(), 1);
+        assert_eq!(view.usable_moves(0), vec![MoveId(1), MoveId(2)]);
+    }
+
+    #[test]
+    fn opposing_bench_stays_hidden_until_sent_in() {
+        let moves = MockMoveRegistry::new();
+        let mut battle = battle(&moves);
+        let lead = battle.active(1, 0).id;
+        let bench = battle.parties[1].creatures[1].id;
+
+        let seen: Vec<_> = battle
+            .view(0)
+            .revealed_opponents()
+            .iter()
+            .map(|c| c.id)
+            .collect();
+        assert_eq!(seen, vec![lead]);
+        assert_eq!(battle.view(0).opponent_team_size(), 2);
+
+        play_turn(
+            &mut battle,
+            vec![BattleAction::Switch {
+                out_id: lead,
+                in_id: bench,
+            }],
+        );
+
+        let seen: Vec<_> = battle
+            .view(0)
+            .revealed_opponents()
+            .iter()
+            .map(|c| c.id)
+            .collect();
+        assert_eq!(seen, vec![bench, lead]);
+    }
+
+    #[test]
+    fn opposing_moves_are_seen_once_used() {
+        let moves = MockMoveRegistry::new();
+        let mut battle = battle(&moves);
+        assert!(battle.view(0).opponent_actives()[0].moves_seen.is_empty());
+
+        let foe_attack = BattleAction::Attack {
+            attacker_id: battle.active(1, 0).id,
+            target_id: battle.active(0, 0).id,
+            move_id: MoveId(2),
+        };
+        play_turn(&mut battle, vec![foe_attack.clone()]);
+        play_turn(&mut battle, vec![foe_attack]);
+
+        assert_eq!(
+            battle.view(0).opponent_actives()[0].moves_seen,
+            vec![MoveId(2)]
+        );
+    }
+
+    #[test]
+    fn opposing_hp_and_status_are_shown_roughly() {
+        let moves = MockMoveRegistry::new();
+        let mut battle = battle(&moves);
+        let foe = &mut battle.parties[1].creatures[0];
+        foe.current_hp = foe.individual_stats.max_hp.get() / 2;
+        foe.status = Some(StatusCondition::Sleep { turns_left: 2 });
+
+        let shown = &battle.view(0).opponent_actives()[0];
+        assert_eq!(shown.hp_percent, 50);
+        assert_eq!(shown.status, Some(StatusKind::Sleep));
+    }
+
+    #[test]
+    fn simulation_only_holds_what_the_side_has_seen() {
+        let moves = MockMoveRegistry::new();
+        let mut battle = battle(&moves);
+        let foe = &mut battle.parties[1].creatures[0];
+        let percent = hp_percent(1, foe.individual_stats.max_hp.get());
+        foe.current_hp = 1;
+        let foe_attack = BattleAction::Attack {
+            attacker_id: battle.active(1, 0).id,
+            target_id: battle.active(0, 0).id,
+            move_id: MoveId(2),
+        };
+        play_turn(&mut battle, vec![foe_attack]);
+
+        let sim = battle.view(0).simulate(3);
+        assert_eq!(sim.parties[1].all().len(), 1);
+        assert_eq!(sim.parties[0].all().len(), 2);
+
+        let foe = sim.active(1, 0);
+        let estimate = Creature::estimated_stats(foe.level);
+        assert_eq!(foe.individual_stats.attack, estimate.attack);
+        assert_eq!(
+            foe.current_hp,
+            (estimate.max_hp.get() as u32 * percent as u32).div_ceil(100) as u16
+        );
+        let known: Vec<_> = foe
+            .moves
+            .iter()
+            .flatten()
+            .map(|m| m.move_id.clone())
+            .collect();
+        assert_eq!(known, vec![MoveId(2)]);
+        assert_eq!(sim.active(0, 0).moves.iter().flatten().count(), 2);
+    }
+
+    #[test]
+    fn simulation_hides_what_the_opponent_picked_this_turn() {
+        let moves = MockMoveRegistry::new();
+        let mut battle = battle(&moves);
+        let water_gun = BattleAction::Attack {
+            attacker_id: battle.active(0, 0).id,
+            target_id: battle.active(1, 0).id,
+            move_id: MoveId(1),
+        };
+        play_turn(&mut battle, vec![water_gun.clone()]);
+        battle.parties[0].creatures[0].moves[0]
+            .as_mut()
+            .unwrap()
+            .pp
+            .current = 3;
+        battle.submit_action(water_gun).unwrap();
+
+        let mut sim = battle.view(1).simulate(3);
+        assert!(sim.current_turn.actions.is_empty());
+        let pp = &sim.active(0, 0).moves[0].as_ref().unwrap().pp;
+        assert_eq!(pp.current, pp.max);
+        assert_eq!(sim.submit_ai_choices(0, &mut GreedyAi), Ok(()));
+        assert_eq!(battle.current_turn.actions.len(), 1);
+    }
+}
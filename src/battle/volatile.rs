@@ -0,0 +1,297 @@
+use super::replay::Intervention;
+use super::timed::{self, Timed};
+use super::{Battle, damage};
+use crate::creature_type::CreatureType;
+use crate::event::BattleEvent;
+use crate::moves::{Move, MoveCategory, MoveRegistry};
+pub use crate::volatile::Volatile;
+
+/// Chance out of 3 that a confused creature hurts itself instead of acting
+const CONFUSION_SELF_HIT_CHANCE: u32 = 1;
+/// Power of the typeless attack a confused creature hits itself with
+const CONFUSION_SELF_HIT_POWER: f32 = 40.0;
+const TAUNT_TURNS: u8 = 3;
+
+#[derive(Debug, Clone, Copy)]
+struct VolatileEntry {
+    condition: Volatile,
+    /// End-of-turn ticks left; `None` lasts until the creature switches out
+    turns_left: Option<u8>,
+    /// Active slot of the creature that caused the condition
+    source: (usize, usize),
+}
+
+impl Timed for VolatileEntry {
+    type Condition = Volatile;
+
+    fn condition(&self) -> Volatile {
+        self.condition
+    }
+
+    fn turns_left(&mut self) -> &mut Option<u8> {
+        &mut self.turns_left
+    }
+}
+
+/// The volatile conditions affecting one active slot
+#[derive(Debug, Clone, Default)]
+pub struct VolatileSet {
+    entries: Vec<VolatileEntry>,
+}
+
+impl VolatileSet {
+    /// Adds a condition; returns `false` if it was already present
+    pub fn add(
+        &mut self,
+        condition: Volatile,
+        turns_left: Option<u8>,
+        source: (usize, usize),
+    ) -> bool {
+        if self.has(condition) {
+            return false;
+        }
+        self.entries.push(VolatileEntry {
+            condition,
+            turns_left,
+            source,
+        });
+        true
+    }
+
+    pub fn has(&self, condition: Volatile) -> bool {
+        self.entries.iter().any(|e| e.condition == condition)
+    }
+
+    /// Slot of the creature that caused `condition`, if present
+    pub fn source(&self, condition: Volatile) -> Option<(usize, usize)> {
+        self.entries
+            .iter()
+            .find(|e| e.condition == condition)
+            .map(|e| e.source)
+    }
+
+    pub fn remove(&mut self, condition: Volatile) -> bool {
+        let before = self.entries.len();
+        self.entries.retain(|e| e.condition != condition);
+        self.entries.len() != before
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Volatile> + '_ {
+        self.entries.iter().map(|e| e.condition)
+    }
+
+    fn set_turns_left(&mut self, condition: Volatile, turns_left: Option<u8>) {
+        if let Some(entry) = self.entries.iter_mut().find(|e| e.condition == condition) {
+            entry.turns_left = turns_left;
+        }
+    }
+
+    /// Counts down every timed condition, returning those that ran out
+    pub fn tick(&mut self) -> Vec<Volatile> {
+        timed::tick(&mut self.entries)
+    }
+}
+
+impl<M: MoveRegistry> Battle<'_, M> {
+    /// The volatile conditions of the creature in an active slot
+    pub fn volatiles(&self, side: usize, slot: usize) -> &VolatileSet {
+        &self.volatiles[side][slot]
+    }
+
+    /// Applies a volatile condition to the creature in `target`
+    ///
+    /// `source` is the slot of the creature causing it. Confusion lasts two to
+    /// five turns as rolled by the battle RNG. Grass types cannot be seeded.
+    pub fn add_volatile(
+        &mut self,
+        target: (usize, usize),
+        condition: Volatile,
+        source: (usize, usize),
+    ) -> bool {
+        self.log_intervention(Intervention::AddVolatile {
+            target,
+            condition,
+            source,
+        });
+        self.apply_volatile(target, condition, source)
+    }
+
+    /// Applies a volatile condition as part of the battle itself, e.g. from a move
+    pub(super) fn apply_volatile(
+        &mut self,
+        (side, slot): (usize, usize),
+        condition: Volatile,
+        source: (usize, usize),
+    ) -> bool {
+        let creature = self.active(side, slot);
+        if creature.is_fainted() {
+            return false;
+        }
+        if condition == Volatile::LeechSeed && creature.types.contains(&CreatureType::Grass) {
+            return false;
+        }
+        let id = creature.id;
+
+        let turns_left = self.roll_volatile_turns(condition);
+        if !self.volatiles[side][slot].add(condition, turns_left, source) {
+            return false;
+        }
+        self.events.push(BattleEvent::VolatileStarted {
+            creature: id,
+            condition,
+        });
+        true
+    }
+
+    /// Rolls how long a new condition lasts; `None` lasts until the creature switches out
+    fn roll_volatile_turns(&mut self, condition: Volatile) -> Option<u8> {
+        match condition {
+            Volatile::Confusion => Some(self.rng.range(2, 5) as u8),
+            Volatile::Flinch | Volatile::Protect => Some(1),
+            Volatile::Taunt => Some(TAUNT_TURNS),
+            Volatile::LeechSeed | Volatile::Trapped => None,
+        }
+    }
+
+    /// Rolls the durations the other side cannot see afresh, such as confusion's
+    pub(super) fn reroll_hidden_volatiles(&mut self, (side, slot): (usize, usize)) {
+        if self.volatiles[side][slot].has(Volatile::Confusion) {
+            let turns_left = self.roll_volatile_turns(Volatile::Confusion);
+            self.volatiles[side][slot].set_turns_left(Volatile::Confusion, turns_left);
+        }
+    }
+
+    /// Checks the volatile conditions that can stop `user` from using `mv`
+    ///
+    /// A confused creature that hurts itself takes the damage here.
+    pub(super) fn volatiles_allow_move(&mut self, user: (usize, usize), mv: &Move) -> bool {
+        let (side, slot) = user;
+        let id = self.active(side, slot).id;
+        let volatiles = &self.volatiles[side][slot];
+
+        let blocked_by = if volatiles.has(Volatile::Flinch) {
+            Some(Volatile::Flinch)
+        } else if volatiles.has(Volatile::Taunt) && mv.category == MoveCategory::Status {
+            Some(Volatile::Taunt)
+        } else if volatiles.has(Volatile::Confusion)
+            && self.rng.chance(CONFUSION_SELF_HIT_CHANCE, 3)
+        {
+            Some(Volatile::Confusion)
+        } else {
+            None
+        };
+
+        let Some(condition) = blocked_by else {
+            return true;
+        };
+        self.events.push(BattleEvent::ActionBlocked {
+            creature: id,
+            condition,
+        });
+        if condition == Volatile::Confusion {
+            let creature = self.active(side, slot);
+            let stats = &creature.individual_stats;
+            let damage = damage::calculate_damage(
+                creature.level,
+                stats.attack.get(),
+                stats.defense.get(),
+                CONFUSION_SELF_HIT_POWER,
+            );
+            self.apply_damage(id, user, damage);
+        }
+        false
+    }
+
+    /// Whether `target` is shielded from a move used by `user`
+    pub(super) fn is_protected_from(&self, user: (usize, usize), target: (usize, usize)) -> bool {
+        user != target && self.volatiles[target.0][target.1].has(Volatile::Protect)
+    }
+
+    /// Drains seeded creatures and counts down volatile durations
+    pub(super) fn volatiles_end_of_turn(&mut self) {
+        for side in 0..self.parties.len() {
+            for slot in 0..self.active_count(side) {
+                self.drain_leech_seed((side, slot));
+            }
+        }
+
+        for side in 0..self.parties.len() {
+            for slot in 0..self.active_count(side) {
+                let id = self.active(side, slot).id;
+                for condition in self.volatiles[side][slot].tick() {
+                    self.events.push(BattleEvent::VolatileEnded {
+                        creature: id,
+                        condition,
+                    });
+                }
+            }
+        }
+    }
+
+    fn drain_leech_seed(&mut self, seeded: (usize, usize)) {
+        let Some(source) = self.volatiles[seeded.0][seeded.1].source(Volatile::LeechSeed) else {
+            return;
+        };
+        let creature = self.active(seeded.0, seeded.1);
+        if creature.is_fainted() {
+            return;
+        }
+        let seeder = self.active(source.0, source.1);
+        let seeder_id = seeder.id;
+        let seeder_fainted = seeder.is_fainted();
+
+        let amount = (creature.individual_stats.max_hp.get() / 8).max(1);
+        let drained = self.apply_damage(seeder_id, seeded, amount);
+        if !seeder_fainted {
+            self.heal(seeder_id, source, drained);
+        }
+    }
+
+    /// Clears everything tied to the creature leaving `slot`
+    pub(super) fn clear_volatiles(&mut self, (side, slot): (usize, usize)) {
+        self.volatiles[side][slot].clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn conditions_are_not_added_twice() {
+        let mut set = VolatileSet::default();
+        assert!(set.add(Volatile::Taunt, Some(3), (1, 0)));
+        assert!(!set.add(Volatile::Taunt, Some(3), (1, 0)));
+        assert!(set.has(Volatile::Taunt));
+        assert_eq!(set.source(Volatile::Taunt), Some((1, 0)));
+    }
+
+    #[test]
+    fn tick_expires_timed_conditions() {
+        let mut set = VolatileSet::default();
+        set.add(Volatile::Flinch, Some(1), (0, 0));
+        set.add(Volatile::Taunt, Some(2), (0, 0));
+        set.add(Volatile::LeechSeed, None, (0, 0));
+
+        assert_eq!(set.tick(), vec![Volatile::Flinch]);
+        assert_eq!(set.tick(), vec![Volatile::Taunt]);
+        assert!(set.tick().is_empty());
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![Volatile::LeechSeed]);
+    }
+
+    #[test]
+    fn remove_and_clear() {
+        let mut set = VolatileSet::default();
+        set.add(Volatile::Confusion, Some(3), (0, 0));
+        set.add(Volatile::Protect, Some(1), (0, 0));
+
+        assert!(set.remove(Volatile::Confusion));
+        assert!(!set.remove(Volatile::Confusion));
+        set.clear();
+        assert!(!set.has(Volatile::Protect));
+    }
+}
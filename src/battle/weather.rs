@@ -0,0 +1,89 @@
+use super::Battle;
+use super::replay::Intervention;
+use crate::creature_type::CreatureType;
+use crate::event::BattleEvent;
+use crate::moves::MoveRegistry;
+pub use crate::weather::Weather;
+
+/// Turns weather lasts when started without an explicit duration
+pub const DEFAULT_WEATHER_TURNS: u8 = 5;
+
+/// Weather currently in effect and how long it has left
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ActiveWeather {
+    pub weather: Weather,
+    /// Turns left; `None` lasts for the whole battle
+    pub turns_left: Option<u8>,
+}
+
+impl<M: MoveRegistry> Battle<'_, M> {
+    /// The weather currently in effect, if any
+    pub fn weather(&self) -> Option<Weather> {
+        self.weather.map(|w| w.weather)
+    }
+
+    /// Starts `weather`, replacing any other weather
+    ///
+    /// Lasts `turns` turns, or the whole battle when `None`. Returns `false`
+    /// if the same weather is already in effect.
+    pub fn set_weather(&mut self, weather: Weather, turns: Option<u8>) -> bool {
+        self.log_intervention(Intervention::SetWeather { weather, turns });
+        self.start_weather(weather, turns)
+    }
+
+    /// Starts weather as part of the battle itself, e.g. from a move
+    pub(super) fn start_weather(&mut self, weather: Weather, turns: Option<u8>) -> bool {
+        if self.weather() == Some(weather) {
+            return false;
+        }
+        self.weather = Some(ActiveWeather {
+            weather,
+            turns_left: turns,
+        });
+        self.events.push(BattleEvent::WeatherStarted { weather });
+        true
+    }
+
+    /// Power multiplier the current weather applies to moves of `move_type`
+    pub(super) fn weather_power_multiplier(&self, move_type: CreatureType) -> f32 {
+        self.weather()
+            .map_or(1.0, |weather| weather.power_multiplier(move_type))
+    }
+
+    /// Deals weather damage to exposed creatures
+    pub(super) fn weather_damage(&mut self) {
+        let Some(weather) = self.weather().filter(|w| w.deals_damage()) else {
+            return;
+        };
+        for side in 0..self.parties.len() {
+            for slot in 0..self.active_count(side) {
+                let creature = self.active(side, slot);
+                if creature.is_fainted() || weather.is_immune(&creature.types) {
+                    continue;
+                }
+                let id = creature.id;
+                let damage = (creature.individual_stats.max_hp.get() / 16).max(1);
+                self.apply_damage(id, (side, slot), damage);
+            }
+        }
+    }
+
+    /// Counts down the weather, ending it when its time is up
+    pub(super) fn weather_end_of_turn(&mut self) {
+        let Some(active) = self.weather.as_mut() else {
+            return;
+        };
+        let weather = active.weather;
+        match &mut active.turns_left {
+            Some(turns) if *turns <= 1 => {
+                self.weather = None;
+                self.events.push(BattleEvent::WeatherEnded { weather });
+            }
+            Some(turns) => {
+                *turns -= 1;
+                self.events.push(BattleEvent::WeatherContinues { weather });
+            }
+            None => self.events.push(BattleEvent::WeatherContinues { weather }),
+        }
+    }
+}
@@ -0,0 +1,11 @@
+/// Stats that can be raised or lowered during a battle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BattleStat {
+    Attack,
+    Defense,
+    SpecialAttack,
+    SpecialDefense,
+    Speed,
+    Accuracy,
+    Evasion,
+}
@@ -1,10 +1,15 @@
+use crate::creature_type::CreatureType;
 use crate::experience::Level;
 use crate::moves::MoveId;
 use crate::species::{Species, SpeciesId};
 use crate::species_registry::SpeciesRegistry;
 use crate::stats::{IndividualStats, Stat};
+use crate::status::StatusCondition;
 use uuid::Uuid;
 
+/// Base stat assumed for every stat when the real ones are unknown
+const ESTIMATED_BASE_STAT: u16 = 80;
+
 /// Globally unique identifier for each persistent creature
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct CreatureId(Uuid);
@@ -35,10 +40,12 @@ pub struct Creature {
     pub id: CreatureId,
     pub species_id: SpeciesId,
     pub name: String,
+    pub types: Vec<CreatureType>,
     pub level: Level,
     pub experience: u32,
     pub individual_stats: IndividualStats,
     pub current_hp: u16, // Plain u16, allows 0 for fainted state
+    pub status: Option<StatusCondition>,
     pub moves: MoveSlots,
 }
 
@@ -47,6 +54,7 @@ impl Creature {
         let id = CreatureId::new();
         let species_id = species.id;
         let name = species.name.clone().to_string();
+        let types = species.types.clone();
         let individual_stats = IndividualStats::from_base(&species.base_stats);
         let level = Level::new(starting_level)?;
         let experience = species.growth_rate.exp_for_level(level);
@@ -55,10 +63,12 @@ impl Creature {
             id,
             species_id,
             name,
+            types,
             level,
             experience,
             individual_stats,
             current_hp: 0,
+            status: None,
             moves: [None, None, None, None],
         };
 
@@ -146,9 +156,29 @@ impl Creature {
         self.individual_stats.max_hp = Self::calculate_hp(stats.max_hp, self.level);
         self.individual_stats.attack = Self::calculate_stat(stats.attack, self.level);
         self.individual_stats.defense = Self::calculate_stat(stats.defense, self.level);
+        self.individual_stats.special_attack =
+            Self::calculate_stat(stats.special_attack, self.level);
+        self.individual_stats.special_defense =
+            Self::calculate_stat(stats.special_defense, self.level);
         self.individual_stats.speed = Self::calculate_stat(stats.speed, self.level);
     }
 
+    /// Stats a creature of middling base stats would have at `level`
+    ///
+    /// Stands in for stats that cannot be seen, such as an opponent's in battle.
+    pub fn estimated_stats(level: Level) -> IndividualStats {
+        let base = Stat::new(ESTIMATED_BASE_STAT).expect("Stat within bounds");
+        let stat = Self::calculate_stat(base, level);
+        IndividualStats {
+            attack: stat,
+            defense: stat,
+            special_attack: stat,
+            special_defense: stat,
+            max_hp: Self::calculate_hp(base, level),
+            speed: stat,
+        }
+    }
+
     fn calculate_hp(hp: Stat, level: Level) -> Stat {
         let hp = (2 * hp.get() * level.get() as u16) / 100 + level.get() as u16 + 10;
         Stat::new(hp).expect("HP within bounds")
@@ -188,6 +218,13 @@ impl Creature {
     pub fn forget_move(&mut self, slot: usize) -> Option<CreatureMove> {
         self.moves.get_mut(slot)?.take()
     }
+
+    /// Refills the PP of every known move, as a healing center does
+    pub fn restore_pp(&mut self) {
+        for known in self.moves.iter_mut().flatten() {
+            known.pp.current = known.pp.max;
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -221,7 +258,7 @@ mod tests {
     use super::*;
     use crate::experience::GrowthRate;
     use crate::species::SpeciesId;
-    use crate::tests::helpers::{MockMoveRegistry, MockRegistry};
+    use crate::tests::helpers::MockRegistry;
 
     fn test_creature(level: u8, registry: &MockRegistry) -> Creature {
         Creature::new(registry.get_species(SpeciesId(1)).unwrap(), level).unwrap()
@@ -282,6 +319,7 @@ mod tests {
         assert_eq!(c.level.get(), 5);
         assert!(c.name().contains("Bulby"));
         assert_eq!(c.individual_stats.attack.get(), 10);
+        assert_eq!(c.individual_stats.special_attack.get(), 10);
     }
 
     #[test]
@@ -356,4 +394,20 @@ mod tests {
             _ => panic!("Expected CanLearnMove event at level 15"),
         }
     }
+
+    #[test]
+    fn restore_pp_refills_every_move() {
+        let registry = MockRegistry::new();
+        let mut c = test_creature(5, &registry);
+        c.try_learn_move(MoveId(1), 10);
+        c.try_learn_move(MoveId(2), 5);
+        for known in c.moves.iter_mut().flatten() {
+            known.pp.current = 0;
+        }
+
+        c.restore_pp();
+
+        let pp: Vec<_> = c.moves.iter().flatten().map(|m| m.pp.current).collect();
+        assert_eq!(pp, vec![10, 5]);
+    }
 }
@@ -5,6 +5,9 @@ pub enum CreatureType {
     Water,
     Grass,
     Electric,
+    Rock,
+    Ground,
+    Ice,
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
@@ -27,13 +30,16 @@ impl Effectiveness {
 }
 
 impl CreatureType {
-    const COUNT: usize = 5;
+    const COUNT: usize = 8;
     const TYPE_CHART: [[u8; Self::COUNT]; Self::COUNT] = [
-        [2, 2, 2, 2, 2],
-        [2, 2, 1, 4, 2],
-        [2, 4, 2, 1, 2],
-        [2, 1, 4, 2, 2],
-        [2, 2, 2, 2, 2],
+        [2, 2, 2, 2, 2, 1, 2, 2],
+        [2, 2, 1, 4, 2, 1, 2, 4],
+        [2, 4, 2, 1, 2, 4, 4, 2],
+        [2, 1, 4, 2, 2, 4, 4, 2],
+        [2, 2, 2, 2, 2, 2, 0, 2],
+        [2, 4, 2, 2, 2, 2, 1, 4],
+        [2, 4, 2, 1, 4, 4, 2, 2],
+        [2, 1, 1, 4, 2, 2, 4, 1],
     ];
 
     pub fn effectiveness(attacker: CreatureType, defender: CreatureType) -> Effectiveness {
@@ -101,6 +107,18 @@ mod tests {
             CreatureType::effectiveness(Grass, Water),
             Effectiveness::Super
         );
+
+        // Electric vs Ground -> Immune
+        assert_eq!(
+            CreatureType::effectiveness(Electric, Ground),
+            Effectiveness::Immune
+        );
+
+        // Rock vs Fire -> Super
+        assert_eq!(
+            CreatureType::effectiveness(Rock, Fire),
+            Effectiveness::Super
+        );
     }
 
     #[test]
@@ -1,7 +1,12 @@
+use crate::battle_stat::BattleStat;
 use crate::creature::CreatureId;
+use crate::side_condition::SideCondition;
+use crate::status::StatusKind;
+use crate::volatile::Volatile;
+use crate::weather::Weather;
 
 /// Represents a battle event
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum BattleEvent {
     Damage {
         source: CreatureId,
@@ -16,10 +21,110 @@ pub enum BattleEvent {
     Fainted {
         creature: CreatureId,
     },
+    SwitchedIn {
+        creature: CreatureId,
+        replacing: CreatureId,
+    },
+    StatusInflicted {
+        creature: CreatureId,
+        status: StatusKind,
+    },
+    StatusCured {
+        creature: CreatureId,
+        status: StatusKind,
+    },
+    /// The creature could not act because of its status
+    Immobilized {
+        creature: CreatureId,
+        status: StatusKind,
+    },
+    VolatileStarted {
+        creature: CreatureId,
+        condition: Volatile,
+    },
+    VolatileEnded {
+        creature: CreatureId,
+        condition: Volatile,
+    },
+    /// A volatile condition stopped the creature from using its move
+    ActionBlocked {
+        creature: CreatureId,
+        condition: Volatile,
+    },
+    StatChanged {
+        creature: CreatureId,
+        stat: BattleStat,
+        change: i8,
+    },
+    /// The stat could not go any higher (or lower)
+    StatLimitReached {
+        creature: CreatureId,
+        stat: BattleStat,
+        raising: bool,
+    },
+    WeatherStarted {
+        weather: Weather,
+    },
+    WeatherContinues {
+        weather: Weather,
+    },
+    WeatherEnded {
+        weather: Weather,
+    },
+    SideConditionStarted {
+        side: usize,
+        condition: SideCondition,
+    },
+    SideConditionEnded {
+        side: usize,
+        condition: SideCondition,
+    },
+    /// An entry hazard hit the creature as it switched in
+    HazardTriggered {
+        creature: CreatureId,
+        hazard: SideCondition,
+    },
+    /// The creature's side ran away from the battle
+    Fled {
+        creature: CreatureId,
+    },
+    /// The creature tried to run away but could not escape
+    FleeFailed {
+        creature: CreatureId,
+    },
+    /// A ball holding the wild creature shook; up to three times
+    BallShook {
+        creature: CreatureId,
+        shakes: u8,
+    },
+    /// A thrown ball caught the wild creature
+    Captured {
+        creature: CreatureId,
+    },
+    /// The wild creature escaped from a thrown ball
+    BrokeFree {
+        creature: CreatureId,
+    },
+    /// The source's move landed a critical hit on the target
+    CriticalHit {
+        source: CreatureId,
+        target: CreatureId,
+    },
+    /// The target shielded itself from the source's move
+    Protected {
+        source: CreatureId,
+        target: CreatureId,
+    },
+    /// The source's move failed to hit the target
     Miss {
         source: CreatureId,
         target: CreatureId,
     },
+    /// The target's types make it immune to the source's move
+    NoEffect {
+        source: CreatureId,
+        target: CreatureId,
+    },
     Custom {
         description: String,
     },
@@ -4,17 +4,28 @@ use crate::event::BattleEvent;
 #[derive(Debug, Default)]
 pub struct EventQueue {
     events: Vec<BattleEvent>,
+    muted: bool,
 }
 
 impl EventQueue {
     /// Create a new empty queue
     pub fn new() -> Self {
-        Self { events: Vec::new() }
+        Self::default()
+    }
+
+    /// Create a queue that drops every event pushed to it
+    pub fn muted() -> Self {
+        Self {
+            events: Vec::new(),
+            muted: true,
+        }
     }
 
     /// Add a new event to the queue
     pub fn push(&mut self, event: BattleEvent) {
-        self.events.push(event);
+        if !self.muted {
+            self.events.push(event);
+        }
     }
 
     /// Check if the queue is empty
@@ -85,5 +96,14 @@ mod tests {
         assert_eq!(drained.len(), 2);
         assert!(queue.is_empty());
     }
-}
 
+    #[test]
+    fn muted_queue_drops_events() {
+        let mut queue = EventQueue::muted();
+        queue.push(BattleEvent::Custom {
+            description: "Ignored".to_string(),
+        });
+
+        assert!(queue.is_empty());
+    }
+}
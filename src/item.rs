@@ -4,6 +4,8 @@ pub struct ItemId(pub u16);
 #[derive(Debug, Clone)]
 pub struct Item {
     // Engine-level behavior flags / data
+    /// Catch chance multiplier when thrown as a ball; `None` for other items
+    pub ball_modifier: Option<f32>,
 }
 
 pub trait ItemRegistry {
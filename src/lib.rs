@@ -1,5 +1,6 @@
 pub mod abilities;
 pub mod battle;
+pub mod battle_stat;
 pub mod creature;
 pub mod creature_type;
 pub mod encounter;
@@ -9,9 +10,15 @@ pub mod experience;
 pub mod item;
 pub mod moves;
 pub mod party;
+pub mod side_condition;
 pub mod species;
 pub mod species_registry;
 pub mod stats;
+pub mod status;
+pub mod storage;
+pub mod volatile;
+pub mod weather;
+pub mod wild_encounter;
 
 #[cfg(test)]
 mod tests;
@@ -1,10 +1,15 @@
+use crate::battle_stat::BattleStat;
 use crate::creature_type::CreatureType;
+use crate::side_condition::SideCondition;
+use crate::status::StatusKind;
+use crate::volatile::Volatile;
+use crate::weather::Weather;
 
 pub trait MoveRegistry {
     fn get(&self, id: &MoveId) -> Option<&Move>;
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct MoveId(pub u16);
 
 impl MoveId {
@@ -13,16 +18,205 @@ impl MoveId {
     }
 }
 
+/// Which creatures a move affects when used
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MoveTarget {
+    /// One other creature chosen by the user, usually an opponent
+    #[default]
+    Single,
+    /// Every opposing creature
+    AllOpponents,
+    /// The user itself
+    User,
+    /// The user's partner in a double battle
+    Ally,
+    /// Every creature except the user
+    AllOthers,
+    /// The battlefield itself rather than any creature
+    Field,
+}
+
+/// Whether a move deals damage and which stats it uses to do so
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MoveCategory {
+    /// Uses attack against defense
+    #[default]
+    Physical,
+    /// Uses special attack against special defense
+    Special,
+    /// Deals no damage
+    Status,
+}
+
+/// How many times a move strikes each of its targets
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HitCount {
+    #[default]
+    Once,
+    /// Always strikes this many times
+    Fixed(u8),
+    /// Strikes a random number of times in `min..=max`
+    Between { min: u8, max: u8 },
+}
+
+/// A share of some amount, such as the damage a move dealt
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Fraction {
+    pub numerator: u16,
+    pub denominator: u16,
+}
+
+impl Fraction {
+    pub const fn new(numerator: u16, denominator: u16) -> Self {
+        Self {
+            numerator,
+            denominator,
+        }
+    }
+
+    /// This share of `amount`, rounded down and capped at `u16::MAX`
+    pub fn of(self, amount: u16) -> u16 {
+        let share = amount as u32 * self.numerator as u32 / self.denominator.max(1) as u32;
+        u16::try_from(share).unwrap_or(u16::MAX)
+    }
+}
+
+/// Which side of a move an effect lands on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EffectTarget {
+    /// The creature using the move, or its side of the field
+    User,
+    /// Each creature the move hit, or the opposing side of the field
+    Target,
+}
+
+/// Something a move does besides dealing damage
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MoveEffect {
+    Status {
+        target: EffectTarget,
+        status: StatusKind,
+    },
+    StatChange {
+        target: EffectTarget,
+        stat: BattleStat,
+        change: i8,
+    },
+    /// Flinch, confusion, protection and other volatile conditions
+    Volatile {
+        target: EffectTarget,
+        condition: Volatile,
+    },
+    Weather(Weather),
+    /// Screens, tailwind and entry hazards
+    SideCondition {
+        target: EffectTarget,
+        condition: SideCondition,
+    },
+    /// Takes a condition such as a screen off a side of the field
+    RemoveSideCondition {
+        target: EffectTarget,
+        condition: SideCondition,
+    },
+    /// Clears every entry hazard from a side of the field
+    ClearHazards {
+        target: EffectTarget,
+    },
+    /// Restores a share of the user's max HP
+    Heal(Fraction),
+}
+
+impl MoveEffect {
+    /// Whether the effect lands on each creature hit rather than once per use
+    pub fn targets_each_hit(&self) -> bool {
+        match self {
+            MoveEffect::Status { target, .. }
+            | MoveEffect::StatChange { target, .. }
+            | MoveEffect::Volatile { target, .. } => *target == EffectTarget::Target,
+            MoveEffect::Weather(_)
+            | MoveEffect::SideCondition { .. }
+            | MoveEffect::RemoveSideCondition { .. }
+            | MoveEffect::ClearHazards { .. }
+            | MoveEffect::Heal(_) => false,
+        }
+    }
+}
+
+/// A move effect with an optional percent chance of activating
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SecondaryEffect {
+    pub effect: MoveEffect,
+    /// Percent chance of activating; `None` always activates
+    pub chance: Option<u8>,
+}
+
+impl SecondaryEffect {
+    /// An effect that always activates
+    pub fn always(effect: MoveEffect) -> Self {
+        Self {
+            effect,
+            chance: None,
+        }
+    }
+
+    /// An effect that activates `percent` times out of 100
+    pub fn with_chance(effect: MoveEffect, percent: u8) -> Self {
+        Self {
+            effect,
+            chance: Some(percent),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Move {
     pub id: MoveId,
     pub name: String,
     pub move_type: CreatureType,
+    pub category: MoveCategory,
     pub power: u8,
     pub max_pp: u8,
+    /// Moves with higher priority act before lower ones regardless of speed
+    pub priority: i8,
+    pub target: MoveTarget,
+    /// Extra critical-hit stages, e.g. 1 for high-crit moves
+    pub crit_stage: u8,
+    /// Percent chance to hit before stages apply; `None` never misses
+    pub accuracy: Option<u8>,
+    pub hits: HitCount,
+    /// Share of the damage dealt that the user takes back
+    pub recoil: Option<Fraction>,
+    /// Share of the damage dealt that the user recovers
+    pub drain: Option<Fraction>,
+    /// Applied in order once the move's damage has been dealt
+    pub effects: Vec<SecondaryEffect>,
 }
 
+/// Id of the built-in Struggle move, which no registry needs to define
+pub const STRUGGLE_ID: MoveId = MoveId(0);
+
 impl Move {
+    /// The move a creature falls back on when it has no PP left, which hurts
+    /// the user for a quarter of the damage it deals
+    pub fn struggle() -> Self {
+        Move {
+            id: STRUGGLE_ID,
+            name: "Struggle".to_string(),
+            move_type: CreatureType::Normal,
+            category: MoveCategory::Physical,
+            power: 50,
+            max_pp: 1,
+            priority: 0,
+            target: MoveTarget::Single,
+            crit_stage: 0,
+            accuracy: None,
+            hits: HitCount::Once,
+            recoil: Some(Fraction::new(1, 4)),
+            drain: None,
+            effects: Vec::new(),
+        }
+    }
+
     fn effectiveness_multiplier(&self, defender_types: &[CreatureType]) -> f32 {
         CreatureType::combined_multiplier(self.move_type, defender_types)
     }
@@ -51,8 +245,17 @@ mod tests {
             id: MoveId(1),
             name: "Flamethrower".to_string(),
             move_type: CreatureType::Fire,
+            category: MoveCategory::Special,
             power: 90,
             max_pp: 15,
+            priority: 0,
+            target: MoveTarget::Single,
+            crit_stage: 0,
+            accuracy: Some(100),
+            hits: HitCount::Once,
+            recoil: None,
+            drain: None,
+            effects: Vec::new(),
         };
 
         // Single defender type
@@ -75,8 +278,17 @@ mod tests {
             id: MoveId(1),
             name: "Flamethrower".to_string(),
             move_type: CreatureType::Fire,
+            category: MoveCategory::Special,
             power: 90,
             max_pp: 15,
+            priority: 0,
+            target: MoveTarget::Single,
+            crit_stage: 0,
+            accuracy: Some(100),
+            hits: HitCount::Once,
+            recoil: None,
+            drain: None,
+            effects: Vec::new(),
         };
 
         // User type does not match move type (no STAB)
@@ -91,8 +303,17 @@ mod tests {
             id: MoveId(1),
             name: "Flamethrower".to_string(),
             move_type: CreatureType::Fire,
+            category: MoveCategory::Special,
             max_pp: 15,
             power: 90,
+            priority: 0,
+            target: MoveTarget::Single,
+            crit_stage: 0,
+            accuracy: Some(100),
+            hits: HitCount::Once,
+            recoil: None,
+            drain: None,
+            effects: Vec::new(),
         };
 
         // User type matches move type (STAB applies)
@@ -107,8 +328,17 @@ mod tests {
             id: MoveId(1),
             name: "Flamethrower".to_string(),
             move_type: CreatureType::Fire,
+            category: MoveCategory::Special,
             power: 90,
             max_pp: 15,
+            priority: 0,
+            target: MoveTarget::Single,
+            crit_stage: 0,
+            accuracy: Some(100),
+            hits: HitCount::Once,
+            recoil: None,
+            drain: None,
+            effects: Vec::new(),
         };
 
         let defenders = [Grass, Water]; // Fire vs Grass = 2, Fire vs Water = 0.5 -> 1.0
@@ -116,4 +346,34 @@ mod tests {
         // STAB = 1.5 -> total = 1.0 * 90 * 1.5 = 135
         assert_eq!(damage, 135.0);
     }
+
+    #[test]
+    fn fraction_takes_share_rounded_down() {
+        assert_eq!(Fraction::new(1, 4).of(10), 2);
+        assert_eq!(Fraction::new(1, 2).of(1), 0);
+        assert_eq!(Fraction::new(1, 0).of(10), 10);
+        assert_eq!(Fraction::new(3, 2).of(10), 15);
+        assert_eq!(Fraction::new(2, 1).of(u16::MAX), u16::MAX);
+    }
+
+    #[test]
+    fn only_creature_effects_on_the_target_apply_per_hit() {
+        let burn = MoveEffect::Status {
+            target: EffectTarget::Target,
+            status: StatusKind::Burn,
+        };
+        let boost = MoveEffect::StatChange {
+            target: EffectTarget::User,
+            stat: BattleStat::Attack,
+            change: 1,
+        };
+        let hazard = MoveEffect::SideCondition {
+            target: EffectTarget::Target,
+            condition: SideCondition::Spikes,
+        };
+        assert!(burn.targets_each_hit());
+        assert!(!boost.targets_each_hit());
+        assert!(!hazard.targets_each_hit());
+        assert!(!MoveEffect::Weather(Weather::Rain).targets_each_hit());
+    }
 }
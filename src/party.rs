@@ -1,14 +1,39 @@
 use crate::creature::Creature;
 
 /// Represents a party of creatures (like Pokémon party)
+#[derive(Debug, Clone)]
 pub struct Party {
-    pub creatures: [Creature; 6], // Fixed size for simplicity
+    pub creatures: Vec<Creature>,
 }
 
 impl Party {
-    /// Create a new party from a fixed array of creatures
-    pub fn new(creatures: [Creature; 6]) -> Self {
-        Self { creatures }
+    /// Most creatures a party can hold
+    pub const MAX_SIZE: usize = 6;
+
+    /// Create a new party from one to six creatures
+    ///
+    /// Returns `None` if `creatures` is empty or holds more than [`Party::MAX_SIZE`].
+    pub fn new(creatures: impl Into<Vec<Creature>>) -> Option<Self> {
+        let creatures = creatures.into();
+        if !(1..=Self::MAX_SIZE).contains(&creatures.len()) {
+            return None;
+        }
+        Some(Self { creatures })
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.creatures.len() >= Self::MAX_SIZE
+    }
+
+    /// Adds a creature to the end of the party
+    ///
+    /// Returns the creature back if the party is already full.
+    pub fn try_add(&mut self, creature: Creature) -> Result<(), Creature> {
+        if self.is_full() {
+            return Err(creature);
+        }
+        self.creatures.push(creature);
+        Ok(())
     }
 
     /// Returns a reference to the first available (non-fainted) creature
@@ -77,7 +102,7 @@ mod tests {
             make_test_creature(19),
             make_test_creature(100),
         ];
-        let party = Party::new(creatures);
+        let party = Party::new(creatures).unwrap();
 
         let active = party.active().unwrap();
         assert_eq!(active.current_hp, 10);
@@ -98,7 +123,7 @@ mod tests {
             c.current_hp = 0;
         }
 
-        let party = Party::new(creatures);
+        let party = Party::new(creatures).unwrap();
 
         assert!(party.all_fainted())
     }
@@ -113,7 +138,7 @@ mod tests {
             make_test_creature(50),
             make_test_creature(60),
         ];
-        let mut party = Party::new(creatures);
+        let mut party = Party::new(creatures).unwrap();
 
         let first_before = party.creatures[0].current_hp;
         let third_before = party.creatures[2].current_hp;
@@ -135,9 +160,31 @@ mod tests {
             make_test_creature(50),
             make_test_creature(60),
         ];
-        let mut party = Party::new(creatures);
+        let mut party = Party::new(creatures).unwrap();
 
         let swapped = party.swap(0, 6); // index 6 is out of bounds
         assert!(!swapped);
     }
+
+    #[test]
+    fn party_can_hold_fewer_than_six() {
+        let party = Party::new([make_test_creature(10)]).unwrap();
+        assert_eq!(party.all().len(), 1);
+        assert!(!party.is_full());
+    }
+
+    #[test]
+    fn try_add_stops_at_six() {
+        let mut party = Party::new(vec![make_test_creature(10); 5]).unwrap();
+        assert!(party.try_add(make_test_creature(20)).is_ok());
+        let rejected = party.try_add(make_test_creature(30)).unwrap_err();
+        assert_eq!(rejected.current_hp, 30);
+        assert_eq!(party.all().len(), Party::MAX_SIZE);
+    }
+
+    #[test]
+    fn party_holds_one_to_six_creatures() {
+        assert!(Party::new(Vec::new()).is_none());
+        assert!(Party::new(vec![make_test_creature(10); 7]).is_none());
+    }
 }
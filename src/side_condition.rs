@@ -0,0 +1,56 @@
+/// Field effects that belong to one side of the battle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SideCondition {
+    /// Halves damage taken from physical attacks
+    Reflect,
+    /// Halves damage taken from special attacks
+    LightScreen,
+    /// Doubles the speed of the side's creatures
+    Tailwind,
+    /// Damages creatures switching in; stacks up to three layers
+    Spikes,
+    /// Damages creatures switching in based on their weakness to Rock
+    StealthRock,
+    /// Poisons creatures switching in; two layers badly poison
+    ToxicSpikes,
+}
+
+impl SideCondition {
+    /// Turns the condition lasts once set; `None` means until removed
+    pub fn duration(self) -> Option<u8> {
+        match self {
+            SideCondition::Reflect | SideCondition::LightScreen => Some(5),
+            SideCondition::Tailwind => Some(4),
+            SideCondition::Spikes | SideCondition::StealthRock | SideCondition::ToxicSpikes => None,
+        }
+    }
+
+    pub fn max_layers(self) -> u8 {
+        match self {
+            SideCondition::Spikes => 3,
+            SideCondition::ToxicSpikes => 2,
+            _ => 1,
+        }
+    }
+
+    /// Whether the condition triggers when a creature switches in
+    pub fn is_hazard(self) -> bool {
+        matches!(
+            self,
+            SideCondition::Spikes | SideCondition::StealthRock | SideCondition::ToxicSpikes
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_entry_hazards_are_hazards() {
+        assert!(SideCondition::StealthRock.is_hazard());
+        assert!(!SideCondition::Reflect.is_hazard());
+        assert_eq!(SideCondition::Reflect.duration(), Some(5));
+        assert_eq!(SideCondition::Spikes.duration(), None);
+    }
+}
@@ -49,6 +49,8 @@ pub struct Species {
     pub growth_rate: GrowthRate,
     pub types: Vec<CreatureType>,
     pub learnset: Vec<LearnableMove>,
+    /// How easily wild members are caught, from 1 (hardest) to 255
+    pub catch_rate: u8,
 }
 
 impl Species {
@@ -59,6 +61,7 @@ impl Species {
         growth_rate: GrowthRate,
         types: Vec<CreatureType>,
         learnset: Vec<LearnableMove>,
+        catch_rate: u8,
     ) -> Self {
         Self {
             id,
@@ -67,6 +70,7 @@ impl Species {
             growth_rate,
             types,
             learnset,
+            catch_rate,
         }
     }
 }
@@ -77,7 +81,7 @@ mod tests {
 
     #[test]
     fn species_creation() {
-        let stats = BaseStats::new(10, 12, 35, 8).unwrap();
+        let stats = BaseStats::new(10, 12, 11, 9, 35, 8).unwrap();
         let ct = vec![CreatureType::Fire];
         let tackle = LearnableMove::new(Level::new(1).unwrap(), MoveId::new(1));
         let learnset = vec![tackle.clone()];
@@ -88,6 +92,7 @@ mod tests {
             GrowthRate::Fast,
             ct,
             learnset,
+            45,
         );
 
         assert_eq!(species.id.0, 1);
@@ -95,6 +100,7 @@ mod tests {
         assert_eq!(species.base_stats.max_hp(), 35);
         assert_eq!(species.growth_rate, GrowthRate::Fast);
         assert_eq!(species.types, vec![CreatureType::Fire]);
-        assert_eq!(species.learnset, vec![tackle])
+        assert_eq!(species.learnset, vec![tackle]);
+        assert_eq!(species.catch_rate, 45);
     }
 }
@@ -37,15 +37,26 @@ impl fmt::Display for Stat {
 pub struct BaseStats {
     pub attack: Stat,
     pub defense: Stat,
+    pub special_attack: Stat,
+    pub special_defense: Stat,
     pub max_hp: Stat,
     pub speed: Stat,
 }
 
 impl BaseStats {
-    pub fn new(attack: u16, defense: u16, max_hp: u16, speed: u16) -> Result<Self, StatError> {
+    pub fn new(
+        attack: u16,
+        defense: u16,
+        special_attack: u16,
+        special_defense: u16,
+        max_hp: u16,
+        speed: u16,
+    ) -> Result<Self, StatError> {
         Ok(Self {
             attack: Stat::new(attack)?,
             defense: Stat::new(defense)?,
+            special_attack: Stat::new(special_attack)?,
+            special_defense: Stat::new(special_defense)?,
             max_hp: Stat::new(max_hp)?,
             speed: Stat::new(speed)?,
         })
@@ -57,6 +68,12 @@ impl BaseStats {
     pub fn defense(&self) -> u16 {
         self.defense.get()
     }
+    pub fn special_attack(&self) -> u16 {
+        self.special_attack.get()
+    }
+    pub fn special_defense(&self) -> u16 {
+        self.special_defense.get()
+    }
     pub fn max_hp(&self) -> u16 {
         self.max_hp.get()
     }
@@ -70,6 +87,8 @@ impl BaseStats {
 pub struct IndividualStats {
     pub attack: Stat,
     pub defense: Stat,
+    pub special_attack: Stat,
+    pub special_defense: Stat,
     pub max_hp: Stat,
     pub speed: Stat,
 }
@@ -80,6 +99,8 @@ impl IndividualStats {
         Self {
             attack: base.attack,
             defense: base.defense,
+            special_attack: base.special_attack,
+            special_defense: base.special_defense,
             max_hp: base.max_hp,
             speed: base.speed,
         }
@@ -99,19 +120,23 @@ mod tests {
 
     #[test]
     fn basestats_creation() {
-        let bs = BaseStats::new(10, 8, 30, 12).unwrap();
+        let bs = BaseStats::new(10, 8, 14, 9, 30, 12).unwrap();
         assert_eq!(bs.attack(), 10);
         assert_eq!(bs.defense(), 8);
+        assert_eq!(bs.special_attack(), 14);
+        assert_eq!(bs.special_defense(), 9);
         assert_eq!(bs.max_hp(), 30);
         assert_eq!(bs.speed(), 12);
     }
 
     #[test]
     fn individualstats_creation() {
-        let bs = BaseStats::new(10, 8, 30, 12).unwrap();
+        let bs = BaseStats::new(10, 8, 14, 9, 30, 12).unwrap();
         let ind = IndividualStats::from_base(&bs);
         assert_eq!(ind.attack.get(), 10);
         assert_eq!(ind.defense.get(), 8);
+        assert_eq!(ind.special_attack.get(), 14);
+        assert_eq!(ind.special_defense.get(), 9);
         assert_eq!(ind.max_hp.get(), 30);
         assert_eq!(ind.speed.get(), 12);
     }
@@ -0,0 +1,154 @@
+use crate::creature_type::CreatureType;
+
+/// Sixteenths of max HP bad poison deals at most, however long it has lasted
+const MAX_TOXIC_SHARE: u8 = 15;
+
+/// The kinds of persistent status problems a creature can suffer from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StatusKind {
+    Poison,
+    BadlyPoisoned,
+    Burn,
+    Paralysis,
+    Sleep,
+    Freeze,
+}
+
+impl StatusKind {
+    /// Whether a creature with the given types can never suffer this status
+    pub fn is_immune(self, types: &[CreatureType]) -> bool {
+        match self {
+            StatusKind::Burn => types.contains(&CreatureType::Fire),
+            StatusKind::Paralysis => types.contains(&CreatureType::Electric),
+            _ => false,
+        }
+    }
+}
+
+/// A persistent status problem, kept on the creature between battles
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatusCondition {
+    Poison,
+    /// Damage grows with every turn spent badly poisoned
+    BadlyPoisoned {
+        turns: u8,
+    },
+    Burn,
+    Paralysis,
+    /// Move attempts left before the creature wakes up
+    Sleep {
+        turns_left: u8,
+    },
+    Freeze,
+}
+
+impl StatusCondition {
+    pub fn kind(self) -> StatusKind {
+        match self {
+            StatusCondition::Poison => StatusKind::Poison,
+            StatusCondition::BadlyPoisoned { .. } => StatusKind::BadlyPoisoned,
+            StatusCondition::Burn => StatusKind::Burn,
+            StatusCondition::Paralysis => StatusKind::Paralysis,
+            StatusCondition::Sleep { .. } => StatusKind::Sleep,
+            StatusCondition::Freeze => StatusKind::Freeze,
+        }
+    }
+
+    /// HP lost at the end of each turn, if this status deals any
+    pub fn end_of_turn_damage(self, max_hp: u16) -> Option<u16> {
+        let damage = match self {
+            StatusCondition::Poison => max_hp / 8,
+            StatusCondition::BadlyPoisoned { turns } => {
+                let share = turns.clamp(1, MAX_TOXIC_SHARE) as u32;
+                (max_hp as u32 * share / 16) as u16
+            }
+            StatusCondition::Burn => max_hp / 16,
+            _ => return None,
+        };
+        Some(damage.max(1))
+    }
+
+    /// Multiplier applied to the creature's attack when it deals damage
+    pub fn attack_multiplier(self) -> f32 {
+        match self {
+            StatusCondition::Burn => 0.5,
+            _ => 1.0,
+        }
+    }
+
+    /// Multiplier applied to the chance of catching the creature
+    pub fn catch_multiplier(self) -> f32 {
+        match self {
+            StatusCondition::Sleep { .. } | StatusCondition::Freeze => 2.0,
+            _ => 1.5,
+        }
+    }
+
+    /// Multiplier applied to the creature's speed
+    pub fn speed_multiplier(self) -> f32 {
+        match self {
+            StatusCondition::Paralysis => 0.5,
+            _ => 1.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn end_of_turn_damage_fractions() {
+        assert_eq!(StatusCondition::Poison.end_of_turn_damage(80), Some(10));
+        assert_eq!(StatusCondition::Burn.end_of_turn_damage(80), Some(5));
+        assert_eq!(
+            StatusCondition::BadlyPoisoned { turns: 3 }.end_of_turn_damage(80),
+            Some(15)
+        );
+        assert_eq!(StatusCondition::Paralysis.end_of_turn_damage(80), None);
+    }
+
+    #[test]
+    fn sleep_and_freeze_help_catching_most() {
+        assert_eq!(StatusCondition::Freeze.catch_multiplier(), 2.0);
+        assert_eq!(
+            StatusCondition::Sleep { turns_left: 2 }.catch_multiplier(),
+            2.0
+        );
+        assert_eq!(StatusCondition::Poison.catch_multiplier(), 1.5);
+    }
+
+    #[test]
+    fn bad_poison_damage_stops_growing_at_fifteen_sixteenths() {
+        let long_poisoned = StatusCondition::BadlyPoisoned { turns: 200 };
+        assert_eq!(long_poisoned.end_of_turn_damage(10_000), Some(9_375));
+        assert_eq!(
+            StatusCondition::BadlyPoisoned { turns: 15 }.end_of_turn_damage(10_000),
+            Some(9_375)
+        );
+    }
+
+    #[test]
+    fn end_of_turn_damage_is_at_least_one() {
+        assert_eq!(StatusCondition::Burn.end_of_turn_damage(10), Some(1));
+    }
+
+    #[test]
+    fn type_immunities() {
+        assert!(StatusKind::Burn.is_immune(&[CreatureType::Fire]));
+        assert!(StatusKind::Paralysis.is_immune(&[CreatureType::Water, CreatureType::Electric]));
+        assert!(!StatusKind::Poison.is_immune(&[CreatureType::Fire]));
+    }
+
+    #[test]
+    fn kind_ignores_counters() {
+        assert_eq!(
+            StatusCondition::Sleep { turns_left: 2 }.kind(),
+            StatusKind::Sleep
+        );
+        assert_eq!(
+            StatusCondition::BadlyPoisoned { turns: 4 }.kind(),
+            StatusKind::BadlyPoisoned
+        );
+    }
+}
@@ -0,0 +1,27 @@
+use crate::creature::Creature;
+
+/// Holds caught creatures that did not fit in the party
+#[derive(Debug, Clone, Default)]
+pub struct Storage {
+    pub creatures: Vec<Creature>,
+}
+
+impl Storage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stores a creature after any already stored
+    pub fn deposit(&mut self, creature: Creature) {
+        self.creatures.push(creature);
+    }
+
+    /// Takes the creature at `index` out of storage
+    pub fn withdraw(&mut self, index: usize) -> Option<Creature> {
+        (index < self.creatures.len()).then(|| self.creatures.remove(index))
+    }
+
+    pub fn all(&self) -> &[Creature] {
+        &self.creatures
+    }
+}
@@ -1,7 +1,12 @@
 use crate::{
+    battle::{Battle, BattleState, format::BattleFormat},
+    creature::Creature,
     creature_type::CreatureType,
+    encounter::Encounter,
     experience::{GrowthRate, Level},
-    moves::{Move, MoveId, MoveRegistry},
+    item::{Item, ItemId, ItemRegistry},
+    moves::{HitCount, Move, MoveCategory, MoveId, MoveRegistry, MoveTarget},
+    party::Party,
     species::{LearnableMove, Species, SpeciesId, SpeciesName},
     species_registry::SpeciesRegistry,
     stats::BaseStats,
@@ -16,9 +21,10 @@ impl MockRegistry {
             species: Box::new(Species {
                 id: SpeciesId(1),
                 name: SpeciesName::new("Bulby"),
-                base_stats: BaseStats::new(50, 50, 50, 50).unwrap(),
+                base_stats: BaseStats::new(50, 50, 50, 50, 50, 50).unwrap(),
                 growth_rate: GrowthRate::Fast,
                 types: vec![CreatureType::Grass],
+                catch_rate: 45,
                 learnset: vec![
                     LearnableMove {
                         level: Level::new(5).unwrap(),
@@ -54,30 +60,151 @@ impl MockMoveRegistry {
                 Move {
                     id: MoveId(1),
                     move_type: CreatureType::Water,
+                    category: MoveCategory::Special,
                     power: 80,
                     name: "Water Gun".to_string(),
                     max_pp: 20,
+                    priority: 0,
+                    target: MoveTarget::Single,
+                    crit_stage: 0,
+                    accuracy: Some(100),
+                    hits: HitCount::Once,
+                    recoil: None,
+                    drain: None,
+                    effects: Vec::new(),
                 },
                 Move {
                     id: MoveId(2),
                     move_type: CreatureType::Fire,
+                    category: MoveCategory::Physical,
                     power: 20,
                     name: "Fire Wheel".to_string(),
                     max_pp: 20,
+                    priority: 0,
+                    target: MoveTarget::Single,
+                    crit_stage: 0,
+                    accuracy: Some(100),
+                    hits: HitCount::Once,
+                    recoil: None,
+                    drain: None,
+                    effects: Vec::new(),
                 },
                 Move {
                     id: MoveId(3),
                     move_type: CreatureType::Grass,
+                    category: MoveCategory::Physical,
                     power: 95,
                     name: "Grass Cut".to_string(),
                     max_pp: 20,
+                    priority: 0,
+                    target: MoveTarget::Single,
+                    crit_stage: 0,
+                    accuracy: Some(100),
+                    hits: HitCount::Once,
+                    recoil: None,
+                    drain: None,
+                    effects: Vec::new(),
                 },
             ],
         }
     }
 }
+impl MockMoveRegistry {
+    /// Mutable access to a move so tests can tweak its data
+    pub fn get_mut(&mut self, id: &MoveId) -> Option<&mut Move> {
+        self.moves.iter_mut().find(|m| &m.id == id)
+    }
+}
+
 impl MoveRegistry for MockMoveRegistry {
     fn get(&self, id: &MoveId) -> Option<&Move> {
         self.moves.iter().find(|m| &m.id == id)
     }
 }
+
+pub struct MockItemRegistry {
+    items: Vec<(ItemId, Item)>,
+}
+impl MockItemRegistry {
+    /// Item 1 is a plain ball, item 2 a better ball and item 3 not a ball
+    pub fn new() -> Self {
+        Self {
+            items: vec![
+                (
+                    ItemId(1),
+                    Item {
+                        ball_modifier: Some(1.0),
+                    },
+                ),
+                (
+                    ItemId(2),
+                    Item {
+                        ball_modifier: Some(2.0),
+                    },
+                ),
+                (
+                    ItemId(3),
+                    Item {
+                        ball_modifier: None,
+                    },
+                ),
+            ],
+        }
+    }
+}
+
+impl ItemRegistry for MockItemRegistry {
+    fn get(&self, id: ItemId) -> Option<&Item> {
+        self.items
+            .iter()
+            .find(|(item_id, _)| *item_id == id)
+            .map(|(_, item)| item)
+    }
+}
+
+/// A creature of the mock species at `level`
+pub fn creature_at(level: u8) -> Creature {
+    let registry = MockRegistry::new();
+    let species = registry.get_species(SpeciesId(1)).unwrap();
+    Creature::new(species, level).unwrap()
+}
+
+/// A full party of six level 5 creatures of the mock species
+pub fn full_party() -> Party {
+    Party::new(std::array::from_fn::<_, 6, _>(|_| creature_at(5))).unwrap()
+}
+
+/// A level 20 creature of the mock species with the given types and moves
+pub fn creature(types: &[CreatureType], moves: &[u16]) -> Creature {
+    let mut creature = creature_at(20);
+    creature.types = types.to_vec();
+    for &id in moves {
+        creature.try_learn_move(MoveId(id), 20);
+    }
+    creature
+}
+
+/// A battle between the two parties, stepped to the first action selection
+pub fn battle(
+    moves: &MockMoveRegistry,
+    format: BattleFormat,
+    ours: Vec<Creature>,
+    theirs: Vec<Creature>,
+) -> Battle<'_, MockMoveRegistry> {
+    let mut battle = Battle::new(
+        Party::new(ours).unwrap(),
+        Party::new(theirs).unwrap(),
+        moves,
+        format,
+        1,
+    );
+    start_selection(&mut battle);
+    battle
+}
+
+/// Steps the battle to the point where actions are picked
+pub fn start_selection<M: MoveRegistry>(battle: &mut Battle<'_, M>) {
+    while battle.state != BattleState::SelectActions {
+        battle.process_turn();
+    }
+}
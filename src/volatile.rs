@@ -0,0 +1,16 @@
+/// In-battle conditions that disappear when the creature leaves the field
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Volatile {
+    /// May attack itself instead of using its move
+    Confusion,
+    /// Loses its action for the rest of the turn
+    Flinch,
+    /// Shielded from other creatures' moves for the rest of the turn
+    Protect,
+    /// Loses HP every turn to heal whoever occupies the seeder's slot
+    LeechSeed,
+    /// Can only use moves that deal damage
+    Taunt,
+    /// Cannot flee or switch out
+    Trapped,
+}
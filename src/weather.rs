@@ -0,0 +1,61 @@
+use crate::creature_type::CreatureType;
+
+/// Weather affecting the whole battlefield
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Weather {
+    Sun,
+    Rain,
+    Sandstorm,
+    Hail,
+}
+
+impl Weather {
+    /// Power multiplier for moves of the given type under this weather
+    pub fn power_multiplier(self, move_type: CreatureType) -> f32 {
+        match (self, move_type) {
+            (Weather::Sun, CreatureType::Fire) | (Weather::Rain, CreatureType::Water) => 1.5,
+            (Weather::Sun, CreatureType::Water) | (Weather::Rain, CreatureType::Fire) => 0.5,
+            _ => 1.0,
+        }
+    }
+
+    /// Whether this weather deals damage at the end of each turn
+    pub fn deals_damage(self) -> bool {
+        matches!(self, Weather::Sandstorm | Weather::Hail)
+    }
+
+    /// Whether a creature with the given types is unaffected by weather damage
+    pub fn is_immune(self, types: &[CreatureType]) -> bool {
+        match self {
+            Weather::Sandstorm => types
+                .iter()
+                .any(|t| matches!(t, CreatureType::Rock | CreatureType::Ground)),
+            Weather::Hail => types.contains(&CreatureType::Ice),
+            Weather::Sun | Weather::Rain => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sun_and_rain_scale_fire_and_water() {
+        assert_eq!(Weather::Sun.power_multiplier(CreatureType::Fire), 1.5);
+        assert_eq!(Weather::Sun.power_multiplier(CreatureType::Water), 0.5);
+        assert_eq!(Weather::Rain.power_multiplier(CreatureType::Water), 1.5);
+        assert_eq!(Weather::Rain.power_multiplier(CreatureType::Fire), 0.5);
+        assert_eq!(Weather::Rain.power_multiplier(CreatureType::Grass), 1.0);
+        assert_eq!(Weather::Hail.power_multiplier(CreatureType::Fire), 1.0);
+    }
+
+    #[test]
+    fn damaging_weather_spares_immune_types() {
+        assert!(Weather::Sandstorm.deals_damage());
+        assert!(!Weather::Sun.deals_damage());
+        assert!(Weather::Sandstorm.is_immune(&[CreatureType::Water, CreatureType::Rock]));
+        assert!(!Weather::Sandstorm.is_immune(&[CreatureType::Ice]));
+        assert!(Weather::Hail.is_immune(&[CreatureType::Ice]));
+    }
+}
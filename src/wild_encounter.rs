@@ -0,0 +1,377 @@
+use crate::battle::ai::RandomAi;
+use crate::battle::error::ActionError;
+use crate::battle::format::{BattleFormat, BattleKind};
+use crate::battle::outcome::BattleResult;
+use crate::battle::{Battle, BattleAction, BattleState};
+use crate::creature::Creature;
+use crate::encounter::Encounter;
+use crate::item::{ItemId, ItemRegistry};
+use crate::moves::MoveRegistry;
+use crate::party::Party;
+use crate::species::Species;
+use crate::storage::Storage;
+
+/// Mixed into the battle seed so the wild creature's choices do not mirror
+/// the battle's own rolls
+const WILD_CHOICE_SALT: u64 = 0x5749_4c44;
+
+/// How a wild encounter ended, from the player's point of view
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WildOutcome {
+    /// The wild creature fainted
+    Defeated,
+    /// The wild creature was caught
+    Captured,
+    /// The player ran away
+    Fled,
+    /// The player ran out of creatures able to fight
+    PlayerLost,
+}
+
+impl WildOutcome {
+    fn from_result(result: BattleResult) -> Self {
+        match result {
+            BattleResult::Won { winner: 0 } => WildOutcome::Defeated,
+            BattleResult::Captured { .. } => WildOutcome::Captured,
+            BattleResult::Fled { .. } => WildOutcome::Fled,
+            BattleResult::Won { .. } | BattleResult::Draw | BattleResult::Forfeit { .. } => {
+                WildOutcome::PlayerLost
+            }
+        }
+    }
+}
+
+/// Where a caught creature was sent
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CaptureDestination {
+    Party,
+    /// The party was full
+    Storage,
+}
+
+/// A single battle against a wild creature, with the player on side 0
+///
+/// The wild creature picks a random move it has PP for every turn; the
+/// player's actions are submitted through [`WildEncounter::battle_mut`] or
+/// the flee and capture helpers.
+pub struct WildEncounter<'a, M: MoveRegistry> {
+    battle: Battle<'a, M>,
+    ai: RandomAi,
+}
+
+impl<'a, M: MoveRegistry> WildEncounter<'a, M> {
+    /// Starts an encounter with a wild `species` at `level`
+    ///
+    /// The wild creature knows the last four moves it could have learned by
+    /// its level. Returns `None` if the level is invalid.
+    pub fn new(
+        player: Party,
+        species: &Species,
+        level: u8,
+        moves: &'a M,
+        seed: u64,
+    ) -> Option<Self> {
+        let mut wild = Creature::new(species, level)?;
+        let learned: Vec<_> = species
+            .learnset
+            .iter()
+            .filter(|m| m.level <= wild.level)
+            .collect();
+        for learnable in learned.iter().rev().take(4).rev() {
+            if let Some(mv) = moves.get(&learnable.move_id) {
+                wild.try_learn_move(learnable.move_id.clone(), mv.max_pp);
+            }
+        }
+
+        let mut battle = Battle::new(
+            player,
+            Party::new([wild])?,
+            moves,
+            BattleFormat::Single,
+            seed,
+        );
+        battle.set_kind(BattleKind::Wild {
+            catch_rate: species.catch_rate,
+        });
+        Some(Self {
+            battle,
+            ai: RandomAi::new(seed ^ WILD_CHOICE_SALT),
+        })
+    }
+
+    pub fn battle(&self) -> &Battle<'a, M> {
+        &self.battle
+    }
+
+    pub fn battle_mut(&mut self) -> &mut Battle<'a, M> {
+        &mut self.battle
+    }
+
+    /// The wild creature being fought
+    pub fn wild(&self) -> &Creature {
+        self.battle.active(1, 0)
+    }
+
+    /// Has the player's active creature try to run away this turn
+    pub fn flee(&mut self) -> Result<(), ActionError> {
+        let creature_id = self.battle.active(0, 0).id;
+        self.battle
+            .submit_action(BattleAction::Flee { creature_id })
+    }
+
+    /// Has the player throw a ball at the wild creature this turn
+    pub fn throw_ball(
+        &mut self,
+        ball: ItemId,
+        items: &impl ItemRegistry,
+    ) -> Result<(), ActionError> {
+        let ball_modifier = items
+            .get(ball)
+            .and_then(|item| item.ball_modifier)
+            .ok_or(ActionError::NotABall { item: ball })?;
+        let thrower_id = self.battle.active(0, 0).id;
+        self.battle.submit_action(BattleAction::Capture {
+            thrower_id,
+            ball_modifier,
+        })
+    }
+
+    /// Whether an action for the wild creature is already queued this turn
+    fn wild_has_chosen(&self) -> bool {
+        let wild = self.wild().id;
+        self.battle
+            .current_turn
+            .actions
+            .iter()
+            .any(|action| action.actor() == Some(wild))
+    }
+
+    /// How the encounter ended, once it is over
+    pub fn outcome(&self) -> Option<WildOutcome> {
+        self.battle
+            .outcome()
+            .map(|outcome| WildOutcome::from_result(outcome.result))
+    }
+
+    /// Ends the encounter, handing back the player's party
+    ///
+    /// A caught creature joins the party, or goes to `storage` if the party
+    /// is full; the returned destination says which.
+    pub fn finish(self, storage: &mut Storage) -> (Party, Option<CaptureDestination>) {
+        let captured = self.outcome() == Some(WildOutcome::Captured);
+        let [mut player, mut wild] = self.battle.into_parties();
+        if !captured {
+            return (player, None);
+        }
+        let caught = wild.creatures.remove(0);
+        let destination = match player.try_add(caught) {
+            Ok(()) => CaptureDestination::Party,
+            Err(caught) => {
+                storage.deposit(caught);
+                CaptureDestination::Storage
+            }
+        };
+        (player, Some(destination))
+    }
+}
+
+impl<M: MoveRegistry> Encounter for WildEncounter<'_, M> {
+    fn process_turn(&mut self) {
+        if self.battle.state == BattleState::SelectActions && !self.wild_has_chosen() {
+            // A choice the battle turns down costs the wild creature its turn
+            let _ = self.battle.submit_ai_choices(1, &mut self.ai);
+        }
+        self.battle.process_turn();
+    }
+
+    fn is_over(&self) -> bool {
+        self.battle.is_over()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::moves::MoveId;
+    use crate::species::SpeciesId;
+    use crate::species_registry::SpeciesRegistry;
+    use crate::stats::Stat;
+    use crate::tests::helpers::{
+        MockItemRegistry, MockMoveRegistry, MockRegistry, creature_at, full_party, start_selection,
+    };
+
+    fn start(moves: &MockMoveRegistry, player_hp: u16) -> WildEncounter<'_, MockMoveRegistry> {
+        let mut player = creature_at(5);
+        player.try_learn_move(MoveId(1), 20);
+        player.current_hp = player_hp;
+        let registry = MockRegistry::new();
+        let species = registry.get_species(SpeciesId(1)).unwrap();
+        WildEncounter::new(Party::new([player]).unwrap(), species, 10, moves, 3).unwrap()
+    }
+
+    fn run_until_over(encounter: &mut WildEncounter<'_, MockMoveRegistry>) {
+        while !encounter.is_over() {
+            if encounter.battle().state == BattleState::SelectActions
+                && encounter.battle().current_turn.actions.is_empty()
+            {
+                encounter
+                    .battle_mut()
+                    .submit_action(BattleAction::Pass)
+                    .unwrap();
+            }
+            encounter.process_turn();
+        }
+    }
+
+    #[test]
+    fn wild_creature_knows_moves_up_to_its_level() {
+        let moves = MockMoveRegistry::new();
+        let encounter = start(&moves, 20);
+
+        let known: Vec<_> = encounter
+            .wild()
+            .moves
+            .iter()
+            .flatten()
+            .map(|m| m.move_id.clone())
+            .collect();
+        assert_eq!(known, vec![MoveId(1), MoveId(2)]);
+        assert_eq!(
+            encounter.battle().kind(),
+            BattleKind::Wild { catch_rate: 45 }
+        );
+        assert_eq!(encounter.battle().parties[1].all().len(), 1);
+    }
+
+    #[test]
+    fn defeating_the_wild_creature() {
+        let moves = MockMoveRegistry::new();
+        let mut encounter = start(&moves, 20);
+        encounter.battle_mut().parties[1].creatures[0].current_hp = 1;
+        start_selection(encounter.battle_mut());
+
+        let attack = BattleAction::Attack {
+            attacker_id: encounter.battle().active(0, 0).id,
+            target_id: encounter.wild().id,
+            move_id: MoveId(1),
+        };
+        encounter.battle_mut().submit_action(attack).unwrap();
+        run_until_over(&mut encounter);
+
+        assert_eq!(encounter.outcome(), Some(WildOutcome::Defeated));
+    }
+
+    #[test]
+    fn wild_creature_keeps_an_action_queued_for_it() {
+        let moves = MockMoveRegistry::new();
+        let mut encounter = start(&moves, 20);
+        start_selection(encounter.battle_mut());
+
+        let wild_attack = BattleAction::Attack {
+            attacker_id: encounter.wild().id,
+            target_id: encounter.battle().active(0, 0).id,
+            move_id: MoveId(2),
+        };
+        let battle = encounter.battle_mut();
+        battle.submit_action(wild_attack).unwrap();
+        battle.submit_action(BattleAction::Pass).unwrap();
+        encounter.process_turn();
+
+        assert_eq!(encounter.battle().state, BattleState::ResolveActions);
+        assert!(matches!(
+            encounter.battle().current_turn.actions[..],
+            [
+                BattleAction::Attack {
+                    move_id: MoveId(2),
+                    ..
+                },
+                BattleAction::Pass
+            ]
+        ));
+    }
+
+    #[test]
+    fn losing_to_the_wild_creature() {
+        let moves = MockMoveRegistry::new();
+        let mut encounter = start(&moves, 1);
+
+        run_until_over(&mut encounter);
+
+        assert_eq!(encounter.outcome(), Some(WildOutcome::PlayerLost));
+    }
+
+    #[test]
+    fn fleeing_from_a_slower_creature() {
+        let moves = MockMoveRegistry::new();
+        let mut encounter = start(&moves, 20);
+        encounter.battle_mut().parties[1].creatures[0]
+            .individual_stats
+            .speed = Stat::new(1).unwrap();
+        start_selection(encounter.battle_mut());
+
+        encounter.flee().unwrap();
+        run_until_over(&mut encounter);
+
+        assert_eq!(encounter.outcome(), Some(WildOutcome::Fled));
+    }
+
+    /// Weakens the wild creature, throws the better ball and plays it out
+    fn capture(encounter: &mut WildEncounter<'_, MockMoveRegistry>) {
+        encounter.battle_mut().parties[1].creatures[0].current_hp = 1;
+        start_selection(encounter.battle_mut());
+        encounter
+            .throw_ball(ItemId(2), &MockItemRegistry::new())
+            .unwrap();
+        run_until_over(encounter);
+    }
+
+    #[test]
+    fn captured_creature_joins_the_party() {
+        let moves = MockMoveRegistry::new();
+        let mut encounter = start(&moves, 20);
+        let wild = encounter.wild().id;
+        capture(&mut encounter);
+        assert_eq!(encounter.outcome(), Some(WildOutcome::Captured));
+
+        let mut storage = Storage::new();
+        let (party, destination) = encounter.finish(&mut storage);
+
+        assert_eq!(destination, Some(CaptureDestination::Party));
+        assert_eq!(party.all().len(), 2);
+        assert_eq!(party.all()[1].id, wild);
+        assert!(storage.all().is_empty());
+    }
+
+    #[test]
+    fn captured_creature_goes_to_storage_when_the_party_is_full() {
+        let moves = MockMoveRegistry::new();
+        let mut party = full_party();
+        for player in party.all_mut() {
+            player.try_learn_move(MoveId(1), 20);
+        }
+        let registry = MockRegistry::new();
+        let species = registry.get_species(SpeciesId(1)).unwrap();
+        let mut encounter = WildEncounter::new(party, species, 10, &moves, 3).unwrap();
+        let wild = encounter.wild().id;
+        capture(&mut encounter);
+
+        let mut storage = Storage::new();
+        let (party, destination) = encounter.finish(&mut storage);
+
+        assert_eq!(destination, Some(CaptureDestination::Storage));
+        assert_eq!(party.all().len(), Party::MAX_SIZE);
+        assert_eq!(storage.all()[0].id, wild);
+    }
+
+    #[test]
+    fn only_balls_can_be_thrown() {
+        let moves = MockMoveRegistry::new();
+        let mut encounter = start(&moves, 20);
+        start_selection(encounter.battle_mut());
+
+        assert_eq!(
+            encounter.throw_ball(ItemId(3), &MockItemRegistry::new()),
+            Err(ActionError::NotABall { item: ItemId(3) })
+        );
+    }
+}